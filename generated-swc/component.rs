@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 use swc_ecma_ast::*;
+use crate::helpers::Timeline;
 
 /// Represents a React component
 #[derive(Clone, Debug)]
@@ -61,6 +62,18 @@ pub struct Component {
     pub structural_templates: Vec<StructuralTemplate>,
     pub conditional_element_templates: HashMap<String, ConditionalElementTemplate>,
     pub expression_templates: Vec<ExpressionTemplate>,
+    // CSS-in-JSX (`css` prop) extraction
+    pub styles: Vec<StyleEntry>,
+    // Event handler closure-capture analysis
+    pub handlers: HashMap<String, HandlerInfo>,
+    // `useTimeline` keyframe analysis, if this component declares one
+    pub timeline: Option<Timeline>,
+    // Other Minimact components (or external components) this one renders
+    pub component_references: Vec<ComponentReference>,
+    /// Non-fatal extraction notes meant for downstream tooling, not the
+    /// file-level `Diagnostic`s `MinimactTransformer` collects - e.g. a
+    /// loop template that shipped without a `key` prop.
+    pub warnings: Vec<String>,
 }
 
 impl Component {
@@ -123,10 +136,44 @@ impl Component {
             structural_templates: Vec::new(),
             conditional_element_templates: HashMap::new(),
             expression_templates: Vec::new(),
+            // CSS-in-JSX (`css` prop) extraction
+            styles: Vec::new(),
+            // Event handler closure-capture analysis
+            handlers: HashMap::new(),
+            // `useTimeline` keyframe analysis, if this component declares one
+            timeline: None,
+            component_references: Vec::new(),
+            warnings: Vec::new(),
         }
     }
 }
 
+/// A JSX tag this component renders that isn't a host element (`div`,
+/// `button`, ...) - either another Minimact component, something from an
+/// external package, or a name with no matching import at all (most likely
+/// a prop/variable holding a component reference rather than a literal
+/// tag). Resolved against the rest of the project by
+/// `link_component_references`.
+#[derive(Clone, Debug)]
+pub struct ComponentReference {
+    pub tag_name: String,
+    pub source: ReferenceSource,
+    /// Filled in by `link_component_references` once the sibling file that
+    /// exports `tag_name` has been identified.
+    pub resolved_class_name: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReferenceSource {
+    /// Imported from a relative path (`./Button`), not yet resolved to a
+    /// specific class - see `ComponentReference::resolved_class_name`.
+    Relative(String),
+    /// Imported from a package - not a local component.
+    External,
+    /// No matching import for this tag name in the current file.
+    Unresolved,
+}
+
 #[derive(Clone, Debug)]
 pub struct Prop {
     pub name: String,
@@ -150,8 +197,19 @@ pub struct UseStateXInfo {
 
 #[derive(Clone, Debug)]
 pub struct UseEffectInfo {
+    /// The effective dependency array the generator re-runs the effect on:
+    /// the author-supplied array if one was written, otherwise the inferred
+    /// set.
     pub dependencies: Vec<String>,
     pub is_client_side: bool,
+    /// Every reactive binding the callback actually reads, regardless of
+    /// what was written in the source - used to diagnose a stale/incomplete
+    /// dependency array (React's exhaustive-deps rule).
+    pub inferred_dependencies: Vec<String>,
+    /// Inferred dependencies missing from an author-supplied array.
+    pub missing_dependencies: Vec<String>,
+    /// Author-supplied entries that aren't actually read by the callback.
+    pub extra_dependencies: Vec<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -248,6 +306,9 @@ pub struct HelperFunction {
     pub params: Vec<FunctionParam>,
     pub return_type: String,
     pub is_async: bool,
+    /// The function body, already translated to indented C# statements -
+    /// see `translate_helper_body`.
+    pub body: String,
 }
 
 #[derive(Clone, Debug)]
@@ -278,10 +339,21 @@ pub struct LoopTemplate {
     pub key_expression: String,
 }
 
+/// One case of a flattened `"switch"` ternary ladder (`a ? <A/> : b ? <B/>
+/// : <C/>`), in source order - a trailing default render (`<C/>` above)
+/// is implicit, the same way a final `else` needs no condition of its own.
+#[derive(Clone, Debug)]
+pub struct SwitchBranch {
+    pub condition_binding: String,
+}
+
 #[derive(Clone, Debug)]
 pub struct StructuralTemplate {
-    pub template_type: String, // "conditional" or "logical"
+    pub template_type: String, // "conditional", "logical", or "switch"
     pub condition_binding: String,
+    /// Populated only for `"switch"` - `condition_binding` above stays
+    /// empty in that case, since a ladder's conditions live here instead.
+    pub branches: Vec<SwitchBranch>,
 }
 
 #[derive(Clone, Debug)]
@@ -291,6 +363,20 @@ pub struct ConditionalElementTemplate {
     pub evaluable: bool,
 }
 
+/// One stage of a chained method-call pipeline like
+/// `items.filter(x => x.active).map(x => x.name).join(", ")`. `method` is
+/// the JS method name and `args` are its literal arguments; when the
+/// argument was an arrow function (`filter`/`map`), `item_var` and
+/// `projected_binding` record the loop variable and the member path it
+/// projects to, the same way `LoopTemplate.item_var` is extracted.
+#[derive(Clone, Debug)]
+pub struct PipelineStage {
+    pub method: String,
+    pub args: Vec<String>,
+    pub item_var: Option<String>,
+    pub projected_binding: Option<String>,
+}
+
 #[derive(Clone, Debug)]
 pub struct ExpressionTemplate {
     pub template_type: String,
@@ -298,6 +384,39 @@ pub struct ExpressionTemplate {
     pub binding: String,
     pub method: Option<String>,
     pub args: Vec<String>,
+    /// Pre-rendered C# form, where one exists - currently only
+    /// `templateLiteral` sets this, to a `$"..."` interpolated string.
+    pub csharp: Option<String>,
+    /// Chained stages, where this is a `"pipeline"` template - empty for
+    /// every other `template_type`.
+    pub stages: Vec<PipelineStage>,
+}
+
+#[derive(Clone, Debug)]
+pub struct StyleEntry {
+    pub class_name: String,
+    pub css: String,
+    pub bindings: Vec<String>,
+    /// Whether the generated class was applied to a host (lowercase) JSX
+    /// element or forwarded to a component via its `className` prop -
+    /// downstream codegen needs this to know whether the class is safe to
+    /// apply directly or must be threaded through as a prop.
+    pub is_host_element: bool,
+}
+
+/// Closure-capture analysis for one JSX event handler attr (`onClick={...}`),
+/// keyed in `Component::handlers` by `"{hexPath}@{eventName}"` - the same
+/// `path@attr` convention `JSXTemplateExtractor` uses for attribute
+/// templates. Tells the server, when it re-executes the handler, which
+/// bindings it needs the current value of vs which setters firing mean a
+/// re-render is coming.
+#[derive(Clone, Debug)]
+pub struct HandlerInfo {
+    pub path: String,
+    pub event_name: String,
+    pub reads: Vec<String>,
+    pub writes: Vec<String>,
+    pub refs: Vec<String>,
 }
 
 // =============================================================================
@@ -401,6 +520,36 @@ pub struct HookMetadata {
     pub jsx_elements: Option<Box<Expr>>,
 }
 
+/// A same-file custom hook (`useX`), analyzed once up front so any
+/// component's call site can inline it without re-walking its body. See
+/// `MinimactTransformer::process_custom_hook`.
+#[derive(Clone, Debug)]
+pub struct CustomHookDefinition {
+    pub name: String,
+    /// Free variables the body references that aren't declared inside it or
+    /// a module global - the hook's actual inputs, derived from usage
+    /// rather than trusted from its declared parameter list.
+    pub inputs: Vec<String>,
+    pub output: HookOutput,
+    /// `useState`/`useClientState` calls found in the body - these can't
+    /// live in a plain method, so they're replayed into the calling
+    /// component's own `use_state` at each call site instead.
+    pub hoisted_state: Vec<UseStateInfo>,
+    pub hoisted_effects: Vec<UseEffectInfo>,
+}
+
+/// What a custom hook's `return` statement produces.
+#[derive(Clone, Debug)]
+pub enum HookOutput {
+    /// No return statement, or a value that can't be destructured.
+    None,
+    /// `return someValue` - the call site's single binding gets it directly.
+    Scalar,
+    /// `return { a, b }` / `return [a, b]` - named fields a destructuring
+    /// call site binds against.
+    Fields(Vec<String>),
+}
+
 #[derive(Clone, Debug)]
 pub struct HookReturnValue {
     pub name: String,
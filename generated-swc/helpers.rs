@@ -4,7 +4,13 @@
 //! For now, they are stubs that provide the correct signatures.
 
 use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
+use swc_common::{FileName, SourceMap};
 use std::collections::{HashMap, HashSet};
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
 use crate::component::*;
 
 // =============================================================================
@@ -13,19 +19,171 @@ use crate::component::*;
 
 /// Convert TypeScript type to C# type
 pub fn ts_type_to_csharp_type(ts_type: &TsType) -> String {
-    // TODO: Generate from tsTypeToCSharpType template
-    "dynamic".to_string()
+    match ts_type {
+        TsType::TsKeywordType(kw) => match kw.kind {
+            TsKeywordTypeKind::TsStringKeyword => "string".to_string(),
+            TsKeywordTypeKind::TsNumberKeyword => "double".to_string(),
+            TsKeywordTypeKind::TsBooleanKeyword => "bool".to_string(),
+            TsKeywordTypeKind::TsVoidKeyword => "void".to_string(),
+            TsKeywordTypeKind::TsNullKeyword | TsKeywordTypeKind::TsUndefinedKeyword => {
+                "object".to_string()
+            }
+            TsKeywordTypeKind::TsAnyKeyword | TsKeywordTypeKind::TsUnknownKeyword => {
+                "object".to_string()
+            }
+            _ => "dynamic".to_string(),
+        },
+        TsType::TsArrayType(arr) => {
+            format!("List<{}>", ts_type_to_csharp_type(&arr.elem_type))
+        }
+        TsType::TsTupleType(tuple) => {
+            let elems: Vec<String> = tuple
+                .elem_types
+                .iter()
+                .map(|elem| ts_type_to_csharp_type(&elem.ty))
+                .collect();
+            format!("({})", elems.join(", "))
+        }
+        TsType::TsParenthesizedType(paren) => ts_type_to_csharp_type(&paren.type_ann),
+        TsType::TsTypeRef(type_ref) => {
+            let TsEntityName::Ident(ident) = &type_ref.type_name else {
+                return "dynamic".to_string();
+            };
+            let name = ident.sym.to_string();
+            let type_args: Option<&Vec<Box<TsType>>> =
+                type_ref.type_params.as_ref().map(|params| &params.params);
+
+            match name.as_str() {
+                "Array" => {
+                    let elem = type_args
+                        .and_then(|args| args.get(0))
+                        .map(|ty| ts_type_to_csharp_type(ty))
+                        .unwrap_or_else(|| "dynamic".to_string());
+                    format!("List<{}>", elem)
+                }
+                "Promise" => {
+                    let inner = type_args
+                        .and_then(|args| args.get(0))
+                        .map(|ty| ts_type_to_csharp_type(ty))
+                        .unwrap_or_else(|| "void".to_string());
+                    if inner == "void" {
+                        "Task".to_string()
+                    } else {
+                        format!("Task<{}>", inner)
+                    }
+                }
+                "Record" => {
+                    let key = type_args
+                        .and_then(|args| args.get(0))
+                        .map(|ty| ts_type_to_csharp_type(ty))
+                        .unwrap_or_else(|| "string".to_string());
+                    let value = type_args
+                        .and_then(|args| args.get(1))
+                        .map(|ty| ts_type_to_csharp_type(ty))
+                        .unwrap_or_else(|| "dynamic".to_string());
+                    format!("Dictionary<{}, {}>", key, value)
+                }
+                "Function" => "Action".to_string(),
+                _ => match type_args {
+                    // Generic type reference (e.g. `Foo<Bar>`) - map the
+                    // arguments recursively and keep the declared name.
+                    Some(args) if !args.is_empty() => {
+                        let mapped: Vec<String> =
+                            args.iter().map(|ty| ts_type_to_csharp_type(ty)).collect();
+                        format!("{}<{}>", name, mapped.join(", "))
+                    }
+                    _ => name,
+                },
+            }
+        }
+        TsType::TsFnOrConstructorType(_) => "Action".to_string(),
+        TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsUnionType(union)) => {
+            union_to_csharp_type(union)
+        }
+        TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsIntersectionType(_)) => {
+            "dynamic".to_string()
+        }
+        TsType::TsLitType(lit) => match &lit.lit {
+            TsLit::Str(_) => "string".to_string(),
+            TsLit::Number(_) => "double".to_string(),
+            TsLit::Bool(_) => "bool".to_string(),
+            _ => "dynamic".to_string(),
+        },
+        // Genuinely unrepresentable in C# (conditional types, mapped types,
+        // `infer`, etc.) - fall back to dynamic rather than guessing.
+        _ => "dynamic".to_string(),
+    }
+}
+
+/// Map a union type to its C# equivalent: `T | null`/`T | undefined` become
+/// the nullable `T?`, and a union of same-kind literals (`"a" | "b"`,
+/// `1 | 2`) collapses to its base type - turning these into a real C# enum
+/// would require naming/emitting a type, which is beyond what this mapper
+/// can do with just a `TsType` in hand.
+fn union_to_csharp_type(union: &TsUnionType) -> String {
+    let is_nullish = |ty: &TsType| {
+        matches!(
+            ty,
+            TsType::TsKeywordType(kw)
+                if matches!(
+                    kw.kind,
+                    TsKeywordTypeKind::TsNullKeyword | TsKeywordTypeKind::TsUndefinedKeyword
+                )
+        )
+    };
+
+    let non_nullish: Vec<&Box<TsType>> = union.types.iter().filter(|ty| !is_nullish(ty)).collect();
+    if non_nullish.len() == union.types.len() || non_nullish.is_empty() {
+        // No null/undefined member - try collapsing a literal union instead.
+        return collapse_literal_union(union).unwrap_or_else(|| "dynamic".to_string());
+    }
+
+    if non_nullish.len() == 1 {
+        let base = ts_type_to_csharp_type(non_nullish[0]);
+        if base == "string" || base == "object" || base == "dynamic" {
+            // Reference types are already nullable in C#.
+            base
+        } else {
+            format!("{}?", base)
+        }
+    } else {
+        "dynamic".to_string()
+    }
+}
+
+/// Collapse a union of same-kind literals (`"a" | "b"`, `1 | 2`) to its base
+/// type. Returns `None` if the members aren't all literals of one kind.
+fn collapse_literal_union(union: &TsUnionType) -> Option<String> {
+    let mut base: Option<&str> = None;
+    for ty in &union.types {
+        let TsType::TsLitType(lit) = &**ty else {
+            return None;
+        };
+        let kind = match &lit.lit {
+            TsLit::Str(_) => "string",
+            TsLit::Number(_) => "double",
+            TsLit::Bool(_) => "bool",
+            _ => return None,
+        };
+        match base {
+            None => base = Some(kind),
+            Some(existing) if existing == kind => {}
+            Some(_) => return None,
+        }
+    }
+    base.map(|b| b.to_string())
 }
 
 /// Infer C# type from JavaScript value
 pub fn infer_csharp_type(value: &Expr) -> String {
-    // TODO: Generate from inferCSharpType template
     match value {
         Expr::Lit(Lit::Str(_)) => "string".to_string(),
         Expr::Lit(Lit::Num(_)) => "int".to_string(),
         Expr::Lit(Lit::Bool(_)) => "bool".to_string(),
         Expr::Array(_) => "List<dynamic>".to_string(),
         Expr::Object(_) => "Dictionary<string, dynamic>".to_string(),
+        Expr::TsAs(as_expr) => ts_type_to_csharp_type(&as_expr.type_ann),
+        Expr::TsConstAssertion(assertion) => infer_csharp_type(&assertion.expr),
         _ => "dynamic".to_string(),
     }
 }
@@ -63,12 +221,16 @@ pub fn escape_csharp_string(s: &str) -> String {
 
 /// Get default value for C# type
 pub fn get_default_value(csharp_type: &str) -> String {
-    if csharp_type.starts_with("List<") {
+    if csharp_type.ends_with('?') {
+        return "null".to_string();
+    }
+    if csharp_type.starts_with("List<") || csharp_type.starts_with("Dictionary<") {
         return format!("new {}()", csharp_type);
     }
 
     match csharp_type {
         "int" => "0".to_string(),
+        "double" => "0".to_string(),
         "bool" => "false".to_string(),
         "string" => "\"\"".to_string(),
         "dynamic" | "object" => "null".to_string(),
@@ -92,9 +254,217 @@ pub fn is_custom_hook_name(name: &str) -> bool {
 // =============================================================================
 
 /// Infer prop types from usage patterns
+///
+/// Walks the component body accumulating, per prop, every observed use that
+/// implies a type - arithmetic/comparisons imply numeric, string
+/// concatenation/template interpolation implies string, guard positions
+/// imply bool, member/method access implies an object shape, `.map`/index
+/// access implies a list, and passing a prop to a known helper function
+/// imposes that parameter's type. Evidence is unified to a single C# type
+/// only when it all agrees; conflicting evidence falls back to `dynamic`
+/// with a diagnostic rather than picking one guess silently.
 pub fn infer_prop_types(component: &mut Component, body: &BlockStmt) {
-    // TODO: Generate from inferPropTypes template
-    // Analyze how props are used in JSX and expressions
+    if component.props.is_empty() {
+        return;
+    }
+
+    let prop_names: HashSet<String> = component.props.iter().map(|p| p.name.clone()).collect();
+    let evidence = {
+        let mut collector = PropConstraintCollector {
+            prop_names,
+            component,
+            evidence: HashMap::new(),
+        };
+        body.visit_with(&mut collector);
+        collector.evidence
+    };
+
+    for prop in &mut component.props {
+        let Some(observed) = evidence.get(&prop.name) else { continue };
+        let (csharp_type, had_conflict) = unify_prop_evidence(observed);
+        if had_conflict {
+            eprintln!(
+                "[Minimact] Prop `{}` on `{}` has conflicting inferred types ({:?}) - defaulting to dynamic",
+                prop.name, component.name, observed
+            );
+        }
+        if csharp_type != "dynamic" {
+            prop.prop_type = csharp_type;
+        }
+    }
+}
+
+/// A single observed use of a prop that constrains its type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum PropTypeEvidence {
+    Numeric,
+    String,
+    Bool,
+    List,
+    Object,
+    /// Imposed by a callee parameter's already-known C# type.
+    Typed(String),
+}
+
+impl PropTypeEvidence {
+    fn csharp_type(&self) -> String {
+        match self {
+            PropTypeEvidence::Numeric => "double".to_string(),
+            PropTypeEvidence::String => "string".to_string(),
+            PropTypeEvidence::Bool => "bool".to_string(),
+            PropTypeEvidence::List => "List<dynamic>".to_string(),
+            PropTypeEvidence::Object => "Dictionary<string, dynamic>".to_string(),
+            PropTypeEvidence::Typed(ty) => ty.clone(),
+        }
+    }
+}
+
+/// Unify a prop's accumulated evidence into one C# type. All evidence must
+/// agree on the same kind to produce anything but `dynamic`; returns
+/// whether the evidence actually conflicted (vs. simply being empty).
+fn unify_prop_evidence(evidence: &[PropTypeEvidence]) -> (String, bool) {
+    let mut kinds: Vec<&PropTypeEvidence> = Vec::new();
+    for e in evidence {
+        if !kinds.contains(&e) {
+            kinds.push(e);
+        }
+    }
+
+    match kinds.as_slice() {
+        [] => ("dynamic".to_string(), false),
+        [single] => (single.csharp_type(), false),
+        _ => ("dynamic".to_string(), true),
+    }
+}
+
+struct PropConstraintCollector<'a> {
+    prop_names: HashSet<String>,
+    component: &'a Component,
+    evidence: HashMap<String, Vec<PropTypeEvidence>>,
+}
+
+impl PropConstraintCollector<'_> {
+    fn as_prop_ident(&self, expr: &Expr) -> Option<String> {
+        if let Expr::Ident(ident) = expr {
+            let name = ident.sym.to_string();
+            if self.prop_names.contains(&name) {
+                return Some(name);
+            }
+        }
+        None
+    }
+
+    fn record(&mut self, name: String, evidence: PropTypeEvidence) {
+        self.evidence.entry(name).or_default().push(evidence);
+    }
+
+    fn record_operand(&mut self, expr: &Expr, evidence: PropTypeEvidence) {
+        if let Some(name) = self.as_prop_ident(expr) {
+            self.record(name, evidence);
+        }
+    }
+
+    fn record_from_bin(&mut self, bin: &BinExpr) {
+        match bin.op {
+            BinaryOp::Add
+            | BinaryOp::Sub
+            | BinaryOp::Mul
+            | BinaryOp::Div
+            | BinaryOp::Mod
+            | BinaryOp::Exp => {
+                // `+` alone is ambiguous between addition and string
+                // concatenation - lean on the other operand when it's a
+                // literal/template of one kind or the other.
+                let other_is_string = matches!(&*bin.left, Expr::Lit(Lit::Str(_)) | Expr::Tpl(_))
+                    || matches!(&*bin.right, Expr::Lit(Lit::Str(_)) | Expr::Tpl(_));
+                let evidence = if bin.op == BinaryOp::Add && other_is_string {
+                    PropTypeEvidence::String
+                } else {
+                    PropTypeEvidence::Numeric
+                };
+                self.record_operand(&bin.left, evidence.clone());
+                self.record_operand(&bin.right, evidence);
+            }
+            BinaryOp::Lt | BinaryOp::LtEq | BinaryOp::Gt | BinaryOp::GtEq => {
+                self.record_operand(&bin.left, PropTypeEvidence::Numeric);
+                self.record_operand(&bin.right, PropTypeEvidence::Numeric);
+            }
+            BinaryOp::LogicalAnd | BinaryOp::LogicalOr => {
+                self.record_operand(&bin.left, PropTypeEvidence::Bool);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Visit for PropConstraintCollector<'_> {
+    fn visit_bin_expr(&mut self, bin: &BinExpr) {
+        self.record_from_bin(bin);
+        bin.visit_children_with(self);
+    }
+
+    fn visit_unary_expr(&mut self, unary: &UnaryExpr) {
+        if unary.op == UnaryOp::Bang {
+            self.record_operand(&unary.arg, PropTypeEvidence::Bool);
+        }
+        unary.visit_children_with(self);
+    }
+
+    fn visit_cond_expr(&mut self, cond: &CondExpr) {
+        self.record_operand(&cond.test, PropTypeEvidence::Bool);
+        cond.visit_children_with(self);
+    }
+
+    fn visit_if_stmt(&mut self, stmt: &IfStmt) {
+        self.record_operand(&stmt.test, PropTypeEvidence::Bool);
+        stmt.visit_children_with(self);
+    }
+
+    fn visit_tpl(&mut self, tpl: &Tpl) {
+        for expr in &tpl.exprs {
+            self.record_operand(expr, PropTypeEvidence::String);
+        }
+        tpl.visit_children_with(self);
+    }
+
+    fn visit_member_expr(&mut self, member: &MemberExpr) {
+        if let Some(name) = self.as_prop_ident(&member.obj) {
+            match &member.prop {
+                MemberProp::Ident(ident) if ident.sym == *"map" => {
+                    self.record(name, PropTypeEvidence::List);
+                }
+                MemberProp::Computed(_) => {
+                    self.record(name, PropTypeEvidence::List);
+                }
+                MemberProp::Ident(_) => {
+                    self.record(name, PropTypeEvidence::Object);
+                }
+                MemberProp::PrivateName(_) => {}
+            }
+        }
+        member.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Callee::Expr(callee) = &call.callee {
+            if let Expr::Ident(ident) = &**callee {
+                let callee_name = ident.sym.to_string();
+                if let Some(helper) = self
+                    .component
+                    .helper_functions
+                    .iter()
+                    .find(|h| h.name == callee_name)
+                {
+                    for (arg, param) in call.args.iter().zip(&helper.params) {
+                        if param.param_type != "dynamic" {
+                            self.record_operand(&arg.expr, PropTypeEvidence::Typed(param.param_type.clone()));
+                        }
+                    }
+                }
+            }
+        }
+        call.visit_children_with(self);
+    }
 }
 
 // =============================================================================
@@ -191,9 +561,195 @@ pub fn validate_plugin_usage(usages: &[PluginUsage]) {
 // =============================================================================
 
 /// Analyze timeline usage
+///
+/// Finds a `useTimeline([...])` call inside `func` and turns its keyframe
+/// array (`{ time, state, value }` object literals) into a sorted
+/// `Timeline`. Each keyframe's `state` is validated against the states this
+/// same function declares with `useState`/`useClientState`; out-of-order
+/// times are clamped rather than rejected, and unknown states or
+/// type-incompatible values are reported as diagnostics instead of
+/// silently dropping the keyframe.
 pub fn analyze_timeline(func: &FnDecl, component_name: &str) -> Option<Timeline> {
-    // TODO: Generate from analyzeTimeline template
-    None
+    let body = func.function.body.as_ref()?;
+    let known_states = collect_state_types(body);
+
+    let mut finder = TimelineCallFinder { keyframes_arg: None };
+    body.visit_with(&mut finder);
+    let keyframes_expr = finder.keyframes_arg?;
+
+    let Expr::Array(array) = &*keyframes_expr else {
+        eprintln!(
+            "[Minimact] useTimeline in `{}` expects an array of keyframes",
+            component_name
+        );
+        return None;
+    };
+
+    let mut keyframes = Vec::new();
+    let mut state_bindings = HashSet::new();
+
+    for elem in array.elems.iter().flatten() {
+        let Expr::Object(obj) = &*elem.expr else { continue };
+
+        let mut time: Option<u32> = None;
+        let mut state: Option<String> = None;
+        let mut value_expr: Option<&Expr> = None;
+
+        for prop in &obj.props {
+            let PropOrSpread::Prop(prop) = prop else { continue };
+            let swc_ecma_ast::Prop::KeyValue(kv) = &**prop else { continue };
+            let key = match &kv.key {
+                PropName::Ident(ident) => ident.sym.to_string(),
+                PropName::Str(s) => s.value.to_string(),
+                _ => continue,
+            };
+
+            match key.as_str() {
+                "time" | "offset" => {
+                    if let Expr::Lit(Lit::Num(n)) = &*kv.value {
+                        time = Some(n.value.max(0.0) as u32);
+                    }
+                }
+                "state" => match &*kv.value {
+                    Expr::Lit(Lit::Str(s)) => state = Some(s.value.to_string()),
+                    Expr::Ident(ident) => state = Some(ident.sym.to_string()),
+                    _ => {}
+                },
+                "value" => value_expr = Some(&kv.value),
+                _ => {}
+            }
+        }
+
+        let (Some(time), Some(state)) = (time, state) else { continue };
+
+        let Some(state_type) = known_states.get(&state) else {
+            eprintln!(
+                "[Minimact] Timeline in `{}` targets unknown state `{}`",
+                component_name, state
+            );
+            continue;
+        };
+
+        if let Some(value_expr) = value_expr {
+            let value_type = infer_csharp_type(value_expr);
+            if state_type != "dynamic" && value_type != "dynamic" && value_type != *state_type {
+                eprintln!(
+                    "[Minimact] Timeline in `{}` assigns a {} value to state `{}` ({})",
+                    component_name, value_type, state, state_type
+                );
+            }
+        }
+
+        let value = value_expr
+            .map(|expr| generate_csharp_expression(Some(expr)))
+            .unwrap_or_else(|| "null".to_string());
+
+        state_bindings.insert(state.clone());
+        keyframes.push(Keyframe { time, state, value });
+    }
+
+    if keyframes.is_empty() {
+        return None;
+    }
+
+    // Clamp rather than reject a hand-written non-increasing timestamp -
+    // a keyframe can never run before the one before it.
+    let mut last_time = 0;
+    for keyframe in &mut keyframes {
+        if keyframe.time < last_time {
+            eprintln!(
+                "[Minimact] Timeline in `{}` has a non-increasing keyframe time at state `{}` - clamped to {}",
+                component_name, keyframe.state, last_time
+            );
+            keyframe.time = last_time;
+        }
+        last_time = keyframe.time;
+    }
+
+    keyframes.sort_by_key(|keyframe| keyframe.time);
+
+    let duration = keyframes.iter().map(|keyframe| keyframe.time).max().unwrap_or(0);
+
+    Some(Timeline {
+        duration,
+        keyframes,
+        state_bindings,
+    })
+}
+
+struct TimelineCallFinder {
+    keyframes_arg: Option<Box<Expr>>,
+}
+
+impl Visit for TimelineCallFinder {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if self.keyframes_arg.is_some() {
+            return;
+        }
+        if let Callee::Expr(callee) = &call.callee {
+            if let Expr::Ident(ident) = &**callee {
+                if ident.sym == *"useTimeline" {
+                    if let Some(arg) = call.args.get(0) {
+                        self.keyframes_arg = Some(Box::new((*arg.expr).clone()));
+                    }
+                    return;
+                }
+            }
+        }
+        call.visit_children_with(self);
+    }
+}
+
+/// Collect the C# type of every `useState`/`useClientState` binding declared
+/// directly in this function, so timeline keyframes can be validated
+/// against them.
+fn collect_state_types(body: &BlockStmt) -> HashMap<String, String> {
+    let mut collector = StateTypeCollector { states: HashMap::new() };
+    body.visit_with(&mut collector);
+    collector.states
+}
+
+struct StateTypeCollector {
+    states: HashMap<String, String>,
+}
+
+impl Visit for StateTypeCollector {
+    fn visit_var_declarator(&mut self, decl: &VarDeclarator) {
+        decl.visit_children_with(self);
+
+        let Some(init) = &decl.init else { return };
+        let Expr::Call(call) = &**init else { return };
+        let Callee::Expr(callee) = &call.callee else { return };
+        let Expr::Ident(ident) = &**callee else { return };
+        if ident.sym != *"useState" && ident.sym != *"useClientState" {
+            return;
+        }
+
+        let Pat::Array(arr) = &decl.name else { return };
+        let Some(state_ident) = arr
+            .elems
+            .get(0)
+            .and_then(|elem| elem.as_ref())
+            .and_then(|pat| pat.as_ident())
+        else {
+            return;
+        };
+
+        let explicit_type = call
+            .type_args
+            .as_ref()
+            .and_then(|args| args.params.get(0))
+            .map(|ty| ts_type_to_csharp_type(ty));
+
+        let state_type = explicit_type.unwrap_or_else(|| {
+            call.args
+                .get(0)
+                .map(|arg| infer_csharp_type(&arg.expr))
+                .unwrap_or_else(|| "dynamic".to_string())
+        });
+
+        self.states.insert(state_ident.id.sym.to_string(), state_type);
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -215,12 +771,189 @@ pub struct Keyframe {
 // =============================================================================
 
 /// Analyze imported hooks from other files
+///
+/// Scans `program`'s import declarations for hook-named bindings, resolves
+/// each relative specifier to a file on disk, parses that file, finds the
+/// matching exported function, and runs `analyze_hook` on it. Results are
+/// memoized in a process-wide cache keyed by resolved path + export name, so
+/// a hook imported by many components is only ever parsed and analyzed
+/// once. Bare package specifiers (no leading `.`) aren't on this filesystem
+/// and are skipped; unresolved imports and cycles are reported to stderr
+/// rather than panicking the transform.
 pub fn analyze_imported_hooks(
     program: &Program,
     file_path: Option<&str>
 ) -> HashMap<String, HookAnalysis> {
-    // TODO: Generate from analyzeImportedHooks template
-    HashMap::new()
+    let mut resolved = HashMap::new();
+
+    let Some(file_path) = file_path else { return resolved; };
+    let Program::Module(module) = program else { return resolved; };
+    let base_dir = Path::new(file_path).parent().unwrap_or_else(|| Path::new("."));
+
+    for item in &module.body {
+        let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item else { continue };
+        let specifier = import.src.value.to_string();
+        if !specifier.starts_with('.') {
+            continue;
+        }
+
+        for spec in &import.specifiers {
+            let (local_name, export_name) = match spec {
+                ImportSpecifier::Named(named) => {
+                    let local = named.local.sym.to_string();
+                    let exported = named
+                        .imported
+                        .as_ref()
+                        .map(|name| match name {
+                            ModuleExportName::Ident(ident) => ident.sym.to_string(),
+                            ModuleExportName::Str(s) => s.value.to_string(),
+                        })
+                        .unwrap_or_else(|| local.clone());
+                    (local, exported)
+                }
+                ImportSpecifier::Default(default) => {
+                    (default.local.sym.to_string(), "default".to_string())
+                }
+                // `import * as hooks` isn't a single hook binding - the call
+                // site would be `hooks.useFoo()`, which the caller resolves.
+                ImportSpecifier::Namespace(_) => continue,
+            };
+
+            if !is_custom_hook_name(&local_name) {
+                continue;
+            }
+
+            let Some(resolved_path) = resolve_module_path(base_dir, &specifier) else {
+                eprintln!(
+                    "[Minimact] Could not resolve imported hook `{}` from `{}`",
+                    local_name, specifier
+                );
+                continue;
+            };
+
+            let cache_key = format!("{}#{}", resolved_path.display(), export_name);
+            if let Some(analysis) = resolve_hook_analysis(&cache_key, &resolved_path, &export_name) {
+                resolved.insert(local_name, analysis);
+            }
+        }
+    }
+
+    resolved
+}
+
+fn imported_hook_cache() -> &'static Mutex<HashMap<String, Option<HookAnalysis>>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, Option<HookAnalysis>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+thread_local! {
+    /// Cache keys currently being resolved on this thread - lets us detect
+    /// an import cycle (hook A imports hook B which imports hook A) instead
+    /// of recursing forever.
+    static RESOLVING_HOOKS: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+}
+
+fn resolve_hook_analysis(
+    cache_key: &str,
+    path: &Path,
+    export_name: &str,
+) -> Option<HookAnalysis> {
+    if let Some(cached) = imported_hook_cache().lock().unwrap().get(cache_key) {
+        return cached.clone();
+    }
+
+    let already_resolving =
+        RESOLVING_HOOKS.with(|resolving| !resolving.borrow_mut().insert(cache_key.to_string()));
+    if already_resolving {
+        eprintln!(
+            "[Minimact] Import cycle detected resolving hook `{}` ({})",
+            export_name, cache_key
+        );
+        return None;
+    }
+
+    let analysis = parse_and_analyze_hook(path, export_name);
+
+    RESOLVING_HOOKS.with(|resolving| {
+        resolving.borrow_mut().remove(cache_key);
+    });
+    imported_hook_cache()
+        .lock()
+        .unwrap()
+        .insert(cache_key.to_string(), analysis.clone());
+
+    analysis
+}
+
+/// Resolve a relative module specifier (`./useFoo`) to a file on disk,
+/// trying the bare path, `.ts`/`.tsx` extensions, and `index.ts`/`index.tsx`.
+fn resolve_module_path(base_dir: &Path, specifier: &str) -> Option<PathBuf> {
+    let candidate = base_dir.join(specifier);
+    if candidate.is_file() {
+        return Some(candidate);
+    }
+
+    for ext in ["tsx", "ts"] {
+        let with_ext = candidate.with_extension(ext);
+        if with_ext.is_file() {
+            return Some(with_ext);
+        }
+    }
+
+    for ext in ["tsx", "ts"] {
+        let index = candidate.join(format!("index.{}", ext));
+        if index.is_file() {
+            return Some(index);
+        }
+    }
+
+    None
+}
+
+fn parse_and_analyze_hook(path: &Path, export_name: &str) -> Option<HookAnalysis> {
+    let source = match std::fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("[Minimact] Failed to read imported hook file {:?}: {}", path, e);
+            return None;
+        }
+    };
+
+    let module = match parse_module_source(path, &source) {
+        Some(module) => module,
+        None => {
+            eprintln!("[Minimact] Failed to parse imported hook file {:?}", path);
+            return None;
+        }
+    };
+
+    let fn_decl = find_exported_fn_decl(&module, export_name)?;
+    analyze_hook(fn_decl)
+}
+
+fn parse_module_source(path: &Path, source: &str) -> Option<Module> {
+    let source_map = SourceMap::default();
+    let source_file = source_map.new_source_file(FileName::Real(path.to_path_buf()), source.to_string());
+
+    let syntax = Syntax::Typescript(TsConfig {
+        tsx: path.extension().map(|ext| ext == "tsx").unwrap_or(false),
+        ..Default::default()
+    });
+
+    let lexer = Lexer::new(syntax, Default::default(), StringInput::from(&*source_file), None);
+    let mut parser = Parser::new_from(lexer);
+    parser.parse_module().ok()
+}
+
+/// Find a top-level `export function <export_name>`. Default exports of an
+/// anonymous function expression aren't representable as a `FnDecl`, so
+/// `export default` is left unresolved for now.
+fn find_exported_fn_decl<'a>(module: &'a Module, export_name: &str) -> Option<&'a FnDecl> {
+    module.body.iter().find_map(|item| {
+        let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) = item else { return None };
+        let Decl::Fn(fn_decl) = &export.decl else { return None };
+        (fn_decl.ident.sym.to_string() == export_name).then_some(fn_decl)
+    })
 }
 
 // =============================================================================
@@ -240,13 +973,26 @@ pub fn extract_use_state(call: &CallExpr, var_name: &Pat, component: &mut Compon
             .and_then(|p| if let Pat::Ident(id) = p { Some(id.id.sym.to_string()) } else { None });
 
         if let Some(name) = state_var {
-            let initial_value = call.args.get(0)
+            // An explicit `useState<Foo>(...)` generic wins over inferring
+            // the type from the initial value.
+            let explicit_type = call
+                .type_args
+                .as_ref()
+                .and_then(|args| args.params.get(0))
+                .map(|ty| ts_type_to_csharp_type(ty));
+
+            let state_type = explicit_type.clone().unwrap_or_else(|| {
+                call.args
+                    .get(0)
+                    .map(|arg| infer_csharp_type(&arg.expr))
+                    .unwrap_or_else(|| "dynamic".to_string())
+            });
+
+            let initial_value = call
+                .args
+                .get(0)
                 .map(|arg| generate_csharp_expression(Some(&arg.expr)))
-                .unwrap_or_else(|| "null".to_string());
-
-            let state_type = call.args.get(0)
-                .map(|arg| infer_csharp_type(&arg.expr))
-                .unwrap_or_else(|| "dynamic".to_string());
+                .unwrap_or_else(|| get_default_value(&state_type));
 
             let info = UseStateInfo {
                 var_name: name.clone(),
@@ -277,16 +1023,38 @@ pub fn extract_use_state_x(call: &CallExpr, var_name: &Pat, component: &mut Comp
 
 /// Extract useEffect
 pub fn extract_use_effect(call: &CallExpr, component: &mut Component) {
-    // TODO: Generate from extractUseEffect template
-    let dependencies = if let Some(arg) = call.args.get(1) {
-        extract_dependency_array(&arg.expr)
-    } else {
-        Vec::new()
+    let declared = call.args.get(1).map(|arg| extract_dependency_array(&arg.expr));
+    let inferred = call
+        .args
+        .get(0)
+        .map(|arg| infer_effect_dependencies(&arg.expr, component))
+        .unwrap_or_default();
+
+    let (dependencies, missing_dependencies, extra_dependencies) = match declared {
+        // No array written at all - fall back to the inferred set instead
+        // of silently running the effect on every render.
+        None => (inferred.clone(), Vec::new(), Vec::new()),
+        Some(declared) => {
+            let missing: Vec<String> = inferred
+                .iter()
+                .filter(|d| !declared.contains(d))
+                .cloned()
+                .collect();
+            let extra: Vec<String> = declared
+                .iter()
+                .filter(|d| !inferred.contains(d))
+                .cloned()
+                .collect();
+            (declared, missing, extra)
+        }
     };
 
     component.use_effect.push(UseEffectInfo {
         dependencies,
         is_client_side: false, // TODO: Analyze callback for client-side APIs
+        inferred_dependencies: inferred,
+        missing_dependencies,
+        extra_dependencies,
     });
 }
 
@@ -304,6 +1072,159 @@ fn extract_dependency_array(expr: &Expr) -> Vec<String> {
     deps
 }
 
+/// Infer a `useEffect` callback's true dependency set (exhaustive-deps):
+/// walk the callback body with a lexical scope stack - same bookkeeping as
+/// `CaptureCollector` - and collect every free identifier that resolves to
+/// one of the component's reactive values (state, props, custom-hook
+/// results) or a `ref.current` read. Setters and a bare reference to the ref
+/// object itself don't belong in a dependency array, so they're excluded.
+pub fn infer_effect_dependencies(callback: &Expr, component: &Component) -> Vec<String> {
+    let mut collector = EffectDependencyCollector::new(component);
+    callback.visit_with(&mut collector);
+    let mut deps: Vec<String> = collector.dependencies.into_iter().collect();
+    deps.sort();
+    deps
+}
+
+struct EffectDependencyCollector<'a> {
+    component: &'a Component,
+    scopes: Vec<HashSet<String>>,
+    dependencies: HashSet<String>,
+}
+
+impl<'a> EffectDependencyCollector<'a> {
+    fn new(component: &'a Component) -> Self {
+        Self {
+            component,
+            scopes: vec![HashSet::new()],
+            dependencies: HashSet::new(),
+        }
+    }
+
+    fn push_scope(&mut self) {
+        self.scopes.push(HashSet::new());
+    }
+
+    fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn bind(&mut self, name: String) {
+        self.scopes.last_mut().expect("at least one scope").insert(name);
+    }
+
+    fn is_locally_bound(&self, name: &str) -> bool {
+        self.scopes.iter().rev().any(|scope| scope.contains(name))
+    }
+
+    fn bind_pat(&mut self, pat: &Pat) {
+        match pat {
+            Pat::Ident(ident) => self.bind(ident.id.sym.to_string()),
+            Pat::Array(arr) => {
+                for elem in arr.elems.iter().flatten() {
+                    self.bind_pat(elem);
+                }
+            }
+            Pat::Object(obj) => {
+                for prop in &obj.props {
+                    match prop {
+                        ObjectPatProp::KeyValue(kv) => self.bind_pat(&kv.value),
+                        ObjectPatProp::Assign(assign) => self.bind(assign.key.sym.to_string()),
+                        ObjectPatProp::Rest(rest) => self.bind_pat(&rest.arg),
+                    }
+                }
+            }
+            Pat::Rest(rest) => self.bind_pat(&rest.arg),
+            Pat::Assign(assign) => self.bind_pat(&assign.left),
+            Pat::Invalid(_) | Pat::Expr(_) => {}
+        }
+    }
+
+    fn is_dependency_value(&self, name: &str) -> bool {
+        self.component
+            .use_state
+            .iter()
+            .chain(&self.component.use_client_state)
+            .any(|s| s.var_name == name)
+            || self.component.props.iter().any(|p| p.name == name)
+            || self
+                .component
+                .custom_hooks
+                .iter()
+                .any(|h| h.instance_name == name || h.return_values.iter().any(|rv| rv == name))
+    }
+
+    fn is_ref(&self, name: &str) -> bool {
+        self.component.use_ref.iter().any(|r| r.name == name)
+    }
+}
+
+impl Visit for EffectDependencyCollector<'_> {
+    fn visit_ident(&mut self, ident: &Ident) {
+        let name = ident.sym.to_string();
+        if !self.is_locally_bound(&name) && self.is_dependency_value(&name) {
+            self.dependencies.insert(name);
+        }
+    }
+
+    fn visit_member_expr(&mut self, member: &MemberExpr) {
+        // Resolve through parens (`(ref).current`) the same way
+        // `build_member_path` resolves a member chain's root, not just a
+        // direct `Expr::Ident` object.
+        if let Some(name) = crate::member_chain_root(&member.obj) {
+            if !self.is_locally_bound(&name) && self.is_ref(&name) {
+                if let MemberProp::Ident(prop) = &member.prop {
+                    if prop.sym.to_string() == "current" {
+                        self.dependencies.insert(name);
+                        return;
+                    }
+                }
+            }
+        }
+        member.visit_children_with(self);
+    }
+
+    fn visit_function(&mut self, func: &Function) {
+        self.push_scope();
+        for param in &func.params {
+            self.bind_pat(&param.pat);
+        }
+        func.visit_children_with(self);
+        self.pop_scope();
+    }
+
+    fn visit_arrow_expr(&mut self, arrow: &ArrowExpr) {
+        self.push_scope();
+        for pat in &arrow.params {
+            self.bind_pat(pat);
+        }
+        arrow.visit_children_with(self);
+        self.pop_scope();
+    }
+
+    fn visit_block_stmt(&mut self, block: &BlockStmt) {
+        self.push_scope();
+        block.visit_children_with(self);
+        self.pop_scope();
+    }
+
+    fn visit_var_declarator(&mut self, decl: &VarDeclarator) {
+        if let Some(init) = &decl.init {
+            init.visit_with(self);
+        }
+        self.bind_pat(&decl.name);
+    }
+
+    fn visit_catch_clause(&mut self, catch: &CatchClause) {
+        self.push_scope();
+        if let Some(param) = &catch.param {
+            self.bind_pat(param);
+        }
+        catch.body.visit_children_with(self);
+        self.pop_scope();
+    }
+}
+
 /// Extract useRef
 pub fn extract_use_ref(call: &CallExpr, var_name: &Pat, component: &mut Component) {
     // TODO: Generate from extractUseRef template
@@ -414,20 +1335,243 @@ pub fn extract_custom_hook_call(call: &CallExpr, var_name: &Pat, hook_name: &str
 // Client-Side Execution Helpers (from extractors/hooks.cjs)
 // =============================================================================
 
-/// Analyze which hooks are used in a function body
-pub fn analyze_hook_usage(callback: &Expr) -> Vec<String> {
-    // TODO: Generate from analyzeHookUsage template
-    Vec::new()
+/// How a free identifier captured by a callback relates to the component's
+/// reactive state - this is what the client-side generator needs in order to
+/// decide what must be serialized/synced and which setters round-trip.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CaptureKind {
+    /// The callback only reads the current value.
+    Read,
+    /// The callback calls a setter (or assigns directly) - the new value
+    /// needs to round-trip back to the server.
+    Write,
+    /// The callback closes over a `useRef` binding.
+    Ref,
+}
+
+/// A single free binding a callback closes over, and how it's used.
+#[derive(Clone, Debug)]
+pub struct CapturedBinding {
+    pub name: String,
+    pub kind: CaptureKind,
+}
+
+/// Walks a callback with a lexical scope stack, recording every
+/// `Pat::Ident` introduced by a parameter, `let`/`const` declarator, or
+/// catch clause as locally bound - only genuinely free identifiers are
+/// resolved against the enclosing component's known bindings. Modeled on
+/// the input/output computation rust-analyzer runs before extracting a
+/// function: walk once, track what's locally declared, and whatever's left
+/// free is what the extracted unit needs from its surroundings.
+struct CaptureCollector<'a> {
+    component: &'a Component,
+    scopes: Vec<HashSet<String>>,
+    captures: HashMap<String, CaptureKind>,
+}
+
+impl<'a> CaptureCollector<'a> {
+    fn new(component: &'a Component) -> Self {
+        Self {
+            component,
+            scopes: vec![HashSet::new()],
+            captures: HashMap::new(),
+        }
+    }
+
+    fn push_scope(&mut self) {
+        self.scopes.push(HashSet::new());
+    }
+
+    fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn bind(&mut self, name: String) {
+        self.scopes.last_mut().expect("at least one scope").insert(name);
+    }
+
+    fn is_locally_bound(&self, name: &str) -> bool {
+        self.scopes.iter().rev().any(|scope| scope.contains(name))
+    }
+
+    /// Record every name a pattern introduces, including destructured params
+    /// (`{ a, b: [c] }`, `...rest`) - not just the simple-identifier case.
+    fn bind_pat(&mut self, pat: &Pat) {
+        match pat {
+            Pat::Ident(ident) => self.bind(ident.id.sym.to_string()),
+            Pat::Array(arr) => {
+                for elem in arr.elems.iter().flatten() {
+                    self.bind_pat(elem);
+                }
+            }
+            Pat::Object(obj) => {
+                for prop in &obj.props {
+                    match prop {
+                        ObjectPatProp::KeyValue(kv) => self.bind_pat(&kv.value),
+                        ObjectPatProp::Assign(assign) => self.bind(assign.key.sym.to_string()),
+                        ObjectPatProp::Rest(rest) => self.bind_pat(&rest.arg),
+                    }
+                }
+            }
+            Pat::Rest(rest) => self.bind_pat(&rest.arg),
+            Pat::Assign(assign) => self.bind_pat(&assign.left),
+            Pat::Invalid(_) | Pat::Expr(_) => {}
+        }
+    }
+
+    /// The default capture kind for a name the component already knows
+    /// about, absent any write observed at a specific call/assignment site.
+    fn known_kind(&self, name: &str) -> Option<CaptureKind> {
+        if self.component.use_ref.iter().any(|r| r.name == name) {
+            return Some(CaptureKind::Ref);
+        }
+
+        let is_known = self
+            .component
+            .use_state
+            .iter()
+            .chain(&self.component.use_client_state)
+            .any(|s| s.var_name == name || s.setter_name.as_deref() == Some(name))
+            || self.component.props.iter().any(|p| p.name == name)
+            || self
+                .component
+                .custom_hooks
+                .iter()
+                .any(|h| h.instance_name == name || h.return_values.iter().any(|rv| rv == name));
+
+        is_known.then_some(CaptureKind::Read)
+    }
+
+    fn is_setter(&self, name: &str) -> bool {
+        self.component
+            .use_state
+            .iter()
+            .chain(&self.component.use_client_state)
+            .any(|s| s.setter_name.as_deref() == Some(name))
+    }
+
+    /// A write observed anywhere wins over a plain read - once a callback is
+    /// known to mutate a binding, it needs a round-trip regardless of how
+    /// many other places merely read it.
+    fn record(&mut self, name: &str, kind: CaptureKind) {
+        if self.is_locally_bound(name) {
+            return;
+        }
+        if self.captures.get(name) != Some(&CaptureKind::Write) {
+            self.captures.insert(name.to_string(), kind);
+        }
+    }
+}
+
+impl Visit for CaptureCollector<'_> {
+    fn visit_ident(&mut self, ident: &Ident) {
+        let name = ident.sym.to_string();
+        if let Some(kind) = self.known_kind(&name) {
+            self.record(&name, kind);
+        }
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Callee::Expr(callee) = &call.callee {
+            if let Expr::Ident(ident) = &**callee {
+                let name = ident.sym.to_string();
+                if !self.is_locally_bound(&name) && self.is_setter(&name) {
+                    self.record(&name, CaptureKind::Write);
+                    for arg in &call.args {
+                        arg.visit_with(self);
+                    }
+                    return;
+                }
+            }
+        }
+        call.visit_children_with(self);
+    }
+
+    fn visit_assign_expr(&mut self, assign: &AssignExpr) {
+        // `x = ...` is a write to `x` itself; every other assignment target
+        // (destructuring, member expressions like `ref.current = ...`) falls
+        // through to default traversal, which resolves the root identifier
+        // as a plain reference.
+        if let PatOrExpr::Pat(pat) = &assign.left {
+            if let Pat::Ident(ident) = &**pat {
+                let name = ident.id.sym.to_string();
+                if let Some(kind) = self.known_kind(&name) {
+                    let kind = if kind == CaptureKind::Ref { CaptureKind::Ref } else { CaptureKind::Write };
+                    self.record(&name, kind);
+                }
+                assign.right.visit_with(self);
+                return;
+            }
+        }
+        assign.visit_children_with(self);
+    }
+
+    fn visit_function(&mut self, func: &Function) {
+        self.push_scope();
+        for param in &func.params {
+            self.bind_pat(&param.pat);
+        }
+        func.visit_children_with(self);
+        self.pop_scope();
+    }
+
+    fn visit_arrow_expr(&mut self, arrow: &ArrowExpr) {
+        self.push_scope();
+        for pat in &arrow.params {
+            self.bind_pat(pat);
+        }
+        arrow.visit_children_with(self);
+        self.pop_scope();
+    }
+
+    fn visit_block_stmt(&mut self, block: &BlockStmt) {
+        self.push_scope();
+        block.visit_children_with(self);
+        self.pop_scope();
+    }
+
+    fn visit_var_declarator(&mut self, decl: &VarDeclarator) {
+        if let Some(init) = &decl.init {
+            init.visit_with(self);
+        }
+        self.bind_pat(&decl.name);
+    }
+
+    fn visit_catch_clause(&mut self, catch: &CatchClause) {
+        self.push_scope();
+        if let Some(param) = &catch.param {
+            self.bind_pat(param);
+        }
+        catch.body.visit_children_with(self);
+        self.pop_scope();
+    }
+}
+
+/// Analyze which reactive bindings a callback closes over: state vars and
+/// their setters, refs, props, and custom-hook return values declared
+/// locally are excluded, and every remaining free identifier is classified
+/// as read-, write-, or ref-captured against `component`.
+pub fn analyze_hook_usage(callback: &Expr, component: &Component) -> Vec<CapturedBinding> {
+    let mut collector = CaptureCollector::new(component);
+    callback.visit_with(&mut collector);
+    collector
+        .captures
+        .into_iter()
+        .map(|(name, kind)| CapturedBinding { name, kind })
+        .collect()
 }
 
 /// Transform effect callback for client-side execution
 pub fn transform_effect_callback(callback: &Expr, hook_calls: &[String]) -> Expr {
-    // TODO: Generate from transformEffectCallback template
+    // TODO: Generate from transformEffectCallback template - rewriting the
+    // body to its client-side form is generator work; `analyze_hook_usage`
+    // is what tells it which captured bindings need serializing first.
     callback.clone()
 }
 
 /// Transform event handler function for client-side execution
 pub fn transform_handler_function(body: &Expr, params: &[Pat], hook_calls: &[String]) -> Expr {
-    // TODO: Generate from transformHandlerFunction template
+    // TODO: Generate from transformHandlerFunction template - see
+    // `transform_effect_callback`.
     body.clone()
 }
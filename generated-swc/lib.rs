@@ -1,6 +1,8 @@
 use swc_ecma_ast::*;
-use swc_ecma_visit::{VisitMut, VisitMutWith};
-use swc_common::DUMMY_SP;
+use swc_ecma_visit::{Visit, VisitMut, VisitMutWith, VisitWith};
+use swc_common::{DUMMY_SP, Span, Spanned};
+use swc_common::{FileName, SourceMap};
+use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
 use std::collections::{HashMap, HashSet};
 use serde::{Serialize, Deserialize};
 use serde_json;
@@ -29,6 +31,26 @@ pub enum ParentContext {
     ArrowFunction,
 }
 
+/// How serious a `Diagnostic` is. Nothing currently escalates a warning to
+/// a hard failure - `generate_outputs` prints every diagnostic it's given
+/// and keeps generating output regardless.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    Warning,
+    Error,
+}
+
+/// A compiler-style note about an unsupported or lossy construct, recorded
+/// as the extractors run instead of silently dropping the offending code.
+/// `span` is the real span of the originating node - resolved back to
+/// file/line/column in `generate_outputs` - never `DUMMY_SP`.
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    pub span: Span,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+}
+
 /// Main Minimact transformer
 pub struct MinimactTransformer {
     /// Stack of parent contexts for path tracking
@@ -45,6 +67,60 @@ pub struct MinimactTransformer {
 
     /// Input file path for output generation
     input_file_path: String,
+
+    /// Class names already present in the source (literal `className`
+    /// values) plus every name generated so far for the `css` prop
+    /// transform, across all components - seeds `ClassNameGenerator` so
+    /// generated names never collide with each other or with hand-written
+    /// ones.
+    used_class_names: HashSet<String>,
+
+    /// Custom hooks (`useX`) defined in this file, analyzed up front so any
+    /// component's call site can look one up regardless of declaration
+    /// order. See `process_custom_hook`.
+    custom_hook_definitions: HashMap<String, CustomHookDefinition>,
+
+    /// Warnings/errors about unsupported or lossy constructs, collected as
+    /// the extractors run and rendered by `generate_outputs`.
+    diagnostics: Vec<Diagnostic>,
+
+    /// Relative imports (`./Button`), keyed by local binding name -
+    /// `collect_component_references` uses this to tell a sibling-file
+    /// component tag apart from an external one or an unresolved name.
+    relative_imports: HashMap<String, RelativeImport>,
+
+    /// What this file exports, by the name another file would `import` it
+    /// as - built alongside `components` for `process_transform_with_exports`.
+    file_exports: FileExports,
+}
+
+/// One relative import specifier, as written, plus which binding form
+/// brought it in.
+#[derive(Clone, Debug)]
+struct RelativeImport {
+    source: String,
+    imported_name: ImportedName,
+}
+
+#[derive(Clone, Debug)]
+enum ImportedName {
+    Default,
+    Named(String),
+    Namespace,
+}
+
+/// What a file exports, by the name another file would `import` it as -
+/// the project-level module resolution pass (`build_module_graph`) needs
+/// this from every file to resolve a relative import to the component it
+/// actually defines.
+#[derive(Clone, Debug, Default)]
+pub struct FileExports {
+    /// `export default function Foo() {}` doesn't currently reach this
+    /// struct - `process_component`/`process_var_decl` only record a
+    /// component's own name, not whether an `ExportDefaultDecl`/
+    /// `ExportDefaultExpr` wrapped it. Left `None` until that's tracked.
+    pub default: Option<String>,
+    pub named: HashSet<String>,
 }
 
 /// Visitor for extracting hooks from component body
@@ -52,6 +128,8 @@ pub struct MinimactTransformer {
 struct HookExtractor<'a> {
     component: &'a mut Component,
     parent_stack: &'a mut Vec<ParentContext>,
+    custom_hook_definitions: &'a HashMap<String, CustomHookDefinition>,
+    diagnostics: &'a mut Vec<Diagnostic>,
 }
 
 /// Visitor for extracting local variables
@@ -63,6 +141,7 @@ struct LocalVariableExtractor<'a> {
 /// Visitor for extracting helper functions
 struct HelperFunctionExtractor<'a> {
     component: &'a mut Component,
+    diagnostics: &'a mut Vec<Diagnostic>,
 }
 
 /// Visitor for capturing render body (return statement)
@@ -81,6 +160,7 @@ struct TemplateExtractor<'a> {
 /// Visitor for tracking external imports
 struct ImportExtractor<'a> {
     external_imports: &'a mut HashSet<String>,
+    relative_imports: &'a mut HashMap<String, RelativeImport>,
 }
 
 /// Visitor for JSX template extraction
@@ -104,6 +184,20 @@ struct ExpressionExtractor<'a> {
     component: &'a mut Component,
 }
 
+/// Visitor that seeds `ClassNameGenerator`'s used-set from any literal
+/// `className="..."` attributes already in the source, so generated
+/// css-prop class names never collide with hand-written ones.
+struct ClassNameCollector<'a> {
+    used: &'a mut HashSet<String>,
+}
+
+/// Visitor that runs closure-capture analysis over each JSX event handler
+/// attr (`onClick={...}`) and records the result in `Component::handlers`,
+/// keyed by the element's hex path - see `HandlerInfo`.
+struct HandlerExtractor<'a> {
+    component: &'a mut Component,
+}
+
 /// Hex path generator for JSX elements
 pub struct HexPathGenerator {
     counter: u32,
@@ -121,6 +215,36 @@ impl HexPathGenerator {
     }
 }
 
+/// Collision-free class-name generator for the `css` prop transform -
+/// mirrors `HexPathGenerator`'s incrementing-counter shape, but keyed per
+/// base name (so `css` on multiple `div`s yields `mm-div`, `mm-div-1`, ...)
+/// and seeded with class names already present in the source.
+pub struct ClassNameGenerator {
+    used: HashSet<String>,
+    counters: HashMap<String, u32>,
+}
+
+impl ClassNameGenerator {
+    pub fn new(used: HashSet<String>) -> Self {
+        Self { used, counters: HashMap::new() }
+    }
+
+    pub fn generate(&mut self, base: &str) -> String {
+        let mut candidate = format!("mm-{}", base);
+        while self.used.contains(&candidate) {
+            let next = self.counters.entry(base.to_string()).or_insert(0);
+            *next += 1;
+            candidate = format!("mm-{}-{}", base, next);
+        }
+        self.used.insert(candidate.clone());
+        candidate
+    }
+
+    pub fn into_used(self) -> HashSet<String> {
+        self.used
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TopLevelFunction {
     pub name: String,
@@ -135,9 +259,24 @@ impl MinimactTransformer {
             top_level_functions: Vec::new(),
             external_imports: HashSet::new(),
             input_file_path,
+            used_class_names: HashSet::new(),
+            custom_hook_definitions: HashMap::new(),
+            diagnostics: Vec::new(),
+            relative_imports: HashMap::new(),
+            file_exports: FileExports::default(),
         }
     }
 
+    /// Record a warning about an unsupported or lossy construct, carrying
+    /// the real span of the node that triggered it.
+    fn warn(&mut self, span: Span, message: impl Into<String>) {
+        self.diagnostics.push(Diagnostic {
+            span,
+            severity: DiagnosticSeverity::Warning,
+            message: message.into(),
+        });
+    }
+
     /// Check if a function is a component (starts with uppercase)
     fn is_component_name(name: &str) -> bool {
         name.chars().next().map(|c| c.is_uppercase()).unwrap_or(false)
@@ -153,17 +292,12 @@ impl MinimactTransformer {
     fn process_component(&mut self, func: &mut FnDecl) {
         let name = func.ident.sym.to_string();
 
-        // Skip non-components
+        // Skip non-components - custom hooks are analyzed separately, in
+        // the first pass over the module (see `process_custom_hook`)
         if !Self::is_component_name(&name) {
             return;
         }
 
-        // Check for custom hooks first
-        if Self::is_custom_hook(&name) {
-            self.process_custom_hook(func);
-            return;
-        }
-
         // Create new component
         let mut component = Component::new(name.clone());
 
@@ -176,6 +310,8 @@ impl MinimactTransformer {
             let mut hook_extractor = HookExtractor {
                 component: &mut component,
                 parent_stack: &mut self.parent_stack,
+                custom_hook_definitions: &self.custom_hook_definitions,
+                diagnostics: &mut self.diagnostics,
             };
             body.visit_mut_with(&mut hook_extractor);
 
@@ -189,6 +325,7 @@ impl MinimactTransformer {
             // 3. Extract helper functions
             let mut func_extractor = HelperFunctionExtractor {
                 component: &mut component,
+                diagnostics: &mut self.diagnostics,
             };
             body.visit_mut_with(&mut func_extractor);
 
@@ -200,36 +337,56 @@ impl MinimactTransformer {
             body.visit_mut_with(&mut render_extractor);
         }
 
+        // Analyze `useTimeline` keyframes, if this component declares one.
+        // `analyze_timeline` only understands a `FnDecl` body, so arrow
+        // components don't get timeline analysis yet.
+        component.timeline = analyze_timeline(func, &name);
+
         // Extract templates from render body (after capturing it)
         if let Some(render_body) = &mut component.render_body.clone() {
             // 5. Assign hex paths to JSX elements
             let mut path_gen = HexPathGenerator::new();
             Self::assign_hex_paths_to_jsx(render_body, &mut path_gen);
 
-            // 6. Extract text and attribute templates
+            // 6. Extract `css` props into generated classes + stylesheet entries
+            let mut class_gen = ClassNameGenerator::new(self.used_class_names.clone());
+            Self::assign_css_classes_to_jsx(render_body, &mut component, &mut class_gen);
+            self.used_class_names = class_gen.into_used();
+
+            // 7. Extract text and attribute templates
             let mut template_extractor = JSXTemplateExtractor {
                 component: &mut component,
                 current_path: Vec::new(),
             };
             render_body.visit_mut_with(&mut template_extractor);
 
-            // 7. Extract loop templates (.map patterns)
+            // 8. Extract loop templates (.map patterns)
             let mut loop_extractor = LoopExtractor {
                 component: &mut component,
             };
             render_body.visit_mut_with(&mut loop_extractor);
 
-            // 8. Extract structural templates (conditionals)
+            // 9. Extract structural templates (conditionals)
             let mut structural_extractor = StructuralExtractor {
                 component: &mut component,
             };
             render_body.visit_mut_with(&mut structural_extractor);
 
-            // 9. Extract expression templates
+            // 10. Extract expression templates
             let mut expr_extractor = ExpressionExtractor {
                 component: &mut component,
             };
             render_body.visit_mut_with(&mut expr_extractor);
+
+            // 11. Capture analysis for event handler attrs
+            let mut handler_extractor = HandlerExtractor {
+                component: &mut component,
+            };
+            render_body.visit_mut_with(&mut handler_extractor);
+
+            // 12. Record which JSX tags reference other components, for
+            // project-level module resolution (`build_module_graph`).
+            Self::collect_component_references(render_body, &mut component, &self.relative_imports, &self.external_imports);
         }
 
         // Add component to list
@@ -307,6 +464,216 @@ impl MinimactTransformer {
         }
     }
 
+    /// Extract `css` props into generated classes, parallel to
+    /// `assign_hex_paths_to_jsx` - same manual JSX recursion, run as its own
+    /// step so the two concerns stay separable.
+    fn assign_css_classes_to_jsx(expr: &mut Expr, component: &mut Component, class_gen: &mut ClassNameGenerator) {
+        match expr {
+            Expr::JSXElement(jsx) => {
+                if let JSXElementName::Ident(ident) = &jsx.opening.name {
+                    let tag_name = ident.sym.to_string();
+                    let css_index = jsx.opening.attrs.iter().position(|attr| {
+                        matches!(attr, JSXAttrOrSpread::JSXAttr(a)
+                            if matches!(&a.name, JSXAttrName::Ident(name) if name.sym.to_string() == "css"))
+                    });
+
+                    if let Some(index) = css_index {
+                        if let JSXAttrOrSpread::JSXAttr(css_attr) = jsx.opening.attrs.remove(index) {
+                            if let Some((css, bindings)) = Self::extract_css_value(&css_attr.value) {
+                                let is_host_element = is_host_tag_name(&tag_name);
+                                let base = if is_host_element { tag_name.clone() } else { to_kebab_case(&tag_name) };
+                                let class_name = class_gen.generate(&base);
+
+                                Self::apply_class_name(&mut jsx.opening.attrs, &class_name);
+
+                                component.styles.push(StyleEntry {
+                                    class_name,
+                                    css,
+                                    bindings,
+                                    is_host_element,
+                                });
+                            }
+                        }
+                    }
+                }
+
+                // Recurse into children
+                for child in &mut jsx.children {
+                    match child {
+                        JSXElementChild::JSXElement(child_jsx) => {
+                            Self::assign_css_classes_to_jsx(&mut Expr::JSXElement(child_jsx.clone()), component, class_gen);
+                        }
+                        JSXElementChild::JSXExprContainer(container) => {
+                            if let JSXExpr::Expr(expr) = &mut container.expr {
+                                Self::assign_css_classes_to_jsx(expr, component, class_gen);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Expr::JSXFragment(frag) => {
+                for child in &mut frag.children {
+                    match child {
+                        JSXElementChild::JSXElement(child_jsx) => {
+                            Self::assign_css_classes_to_jsx(&mut Expr::JSXElement(child_jsx.clone()), component, class_gen);
+                        }
+                        JSXElementChild::JSXExprContainer(container) => {
+                            if let JSXExpr::Expr(expr) = &mut container.expr {
+                                Self::assign_css_classes_to_jsx(expr, component, class_gen);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Expr::Cond(cond) => {
+                Self::assign_css_classes_to_jsx(&mut cond.cons, component, class_gen);
+                Self::assign_css_classes_to_jsx(&mut cond.alt, component, class_gen);
+            }
+            Expr::Paren(paren) => {
+                Self::assign_css_classes_to_jsx(&mut paren.expr, component, class_gen);
+            }
+            _ => {}
+        }
+    }
+
+    /// Walk JSX looking for non-host tags (`<Button>` vs `<div>`) and
+    /// record each as a `ComponentReference`, classified against this
+    /// file's own import tables: a relative import is another Minimact
+    /// component, an external one is a package import, and anything else
+    /// wasn't imported at all in this file - most likely a prop/variable
+    /// holding a component reference rather than a literal tag name.
+    fn collect_component_references(
+        expr: &Expr,
+        component: &mut Component,
+        relative_imports: &HashMap<String, RelativeImport>,
+        external_imports: &HashSet<String>,
+    ) {
+        match expr {
+            Expr::JSXElement(jsx) => {
+                if let JSXElementName::Ident(ident) = &jsx.opening.name {
+                    let tag_name = ident.sym.to_string();
+                    if !is_host_tag_name(&tag_name) {
+                        let source = if let Some(import) = relative_imports.get(&tag_name) {
+                            ReferenceSource::Relative(import.source.clone())
+                        } else if external_imports.contains(&tag_name) {
+                            ReferenceSource::External
+                        } else {
+                            ReferenceSource::Unresolved
+                        };
+                        component.component_references.push(ComponentReference {
+                            tag_name,
+                            source,
+                            resolved_class_name: None,
+                        });
+                    }
+                }
+
+                for child in &jsx.children {
+                    match child {
+                        JSXElementChild::JSXElement(child_jsx) => {
+                            Self::collect_component_references(
+                                &Expr::JSXElement(child_jsx.clone()), component, relative_imports, external_imports,
+                            );
+                        }
+                        JSXElementChild::JSXExprContainer(container) => {
+                            if let JSXExpr::Expr(expr) = &container.expr {
+                                Self::collect_component_references(expr, component, relative_imports, external_imports);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Expr::JSXFragment(frag) => {
+                for child in &frag.children {
+                    match child {
+                        JSXElementChild::JSXElement(child_jsx) => {
+                            Self::collect_component_references(
+                                &Expr::JSXElement(child_jsx.clone()), component, relative_imports, external_imports,
+                            );
+                        }
+                        JSXElementChild::JSXExprContainer(container) => {
+                            if let JSXExpr::Expr(expr) = &container.expr {
+                                Self::collect_component_references(expr, component, relative_imports, external_imports);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Expr::Cond(cond) => {
+                Self::collect_component_references(&cond.cons, component, relative_imports, external_imports);
+                Self::collect_component_references(&cond.alt, component, relative_imports, external_imports);
+            }
+            Expr::Paren(paren) => {
+                Self::collect_component_references(&paren.expr, component, relative_imports, external_imports);
+            }
+            _ => {}
+        }
+    }
+
+    /// Pull the css text and any `${...}` bindings out of a `css` attr's
+    /// value - a plain string literal, or a template literal whose
+    /// interpolations are rendered with the same `${{binding}}` convention
+    /// `generate_template_string` uses for JSX text/attribute templates.
+    fn extract_css_value(value: &Option<JSXAttrValue>) -> Option<(String, Vec<String>)> {
+        match value {
+            Some(JSXAttrValue::Str(s)) => Some((s.value.to_string(), Vec::new())),
+            Some(JSXAttrValue::JSXExprContainer(container)) => match &container.expr {
+                JSXExpr::Expr(expr) => match &**expr {
+                    Expr::Lit(Lit::Str(s)) => Some((s.value.to_string(), Vec::new())),
+                    Expr::Tpl(tpl) => {
+                        let mut css = String::new();
+                        let mut bindings = Vec::new();
+                        for (i, quasi) in tpl.quasis.iter().enumerate() {
+                            css.push_str(&quasi.raw);
+                            if let Some(interpolation) = tpl.exprs.get(i) {
+                                css.push_str(&generate_template_string(interpolation));
+                                bindings.extend(extract_bindings_from_expr(interpolation));
+                            }
+                        }
+                        Some((css, bindings))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Add `class_name` to a JSX element's `className` attribute, merging
+    /// with an existing literal value rather than overwriting it. Leaves a
+    /// dynamic (non-literal) `className` expression alone - merging into an
+    /// arbitrary expression isn't safe to do mechanically.
+    fn apply_class_name(attrs: &mut Vec<JSXAttrOrSpread>, class_name: &str) {
+        for attr in attrs.iter_mut() {
+            if let JSXAttrOrSpread::JSXAttr(a) = attr {
+                if let JSXAttrName::Ident(name) = &a.name {
+                    if name.sym.to_string() == "className" {
+                        if let Some(JSXAttrValue::Str(existing)) = &mut a.value {
+                            existing.value = format!("{} {}", existing.value, class_name).into();
+                            existing.raw = None;
+                        }
+                        return;
+                    }
+                }
+            }
+        }
+
+        attrs.push(JSXAttrOrSpread::JSXAttr(JSXAttr {
+            span: DUMMY_SP,
+            name: JSXAttrName::Ident(IdentName::new("className".into(), DUMMY_SP)),
+            value: Some(JSXAttrValue::Str(Str {
+                span: DUMMY_SP,
+                value: class_name.into(),
+                raw: None,
+            })),
+        }));
+    }
+
     /// Process an arrow function component
     fn process_arrow_component(&mut self, arrow: &mut ArrowExpr, name: String) {
         // Skip non-components
@@ -327,6 +694,8 @@ impl MinimactTransformer {
                 let mut hook_extractor = HookExtractor {
                     component: &mut component,
                     parent_stack: &mut self.parent_stack,
+                    custom_hook_definitions: &self.custom_hook_definitions,
+                    diagnostics: &mut self.diagnostics,
                 };
                 block.visit_mut_with(&mut hook_extractor);
 
@@ -338,6 +707,7 @@ impl MinimactTransformer {
 
                 let mut func_extractor = HelperFunctionExtractor {
                     component: &mut component,
+                    diagnostics: &mut self.diagnostics,
                 };
                 block.visit_mut_with(&mut func_extractor);
 
@@ -380,12 +750,18 @@ impl MinimactTransformer {
             if let TsType::TsTypeLit(type_lit) = &*ann.type_ann {
                 for member in &type_lit.members {
                     if let TsTypeElement::TsPropertySignature(prop_sig) = member {
-                        if let Expr::Ident(ident) = &*prop_sig.key {
-                            let prop_name = ident.sym.to_string();
-                            let prop_type = prop_sig.type_ann.as_ref()
-                                .map(|ann| Self::ts_type_to_csharp(&ann.type_ann))
-                                .unwrap_or_else(|| "dynamic".to_string());
-                            prop_types.insert(prop_name, prop_type);
+                        match &*prop_sig.key {
+                            Expr::Ident(ident) => {
+                                let prop_name = ident.sym.to_string();
+                                let prop_type = match prop_sig.type_ann.as_ref() {
+                                    Some(ann) => self.ts_type_to_csharp(&ann.type_ann),
+                                    None => "dynamic".to_string(),
+                                };
+                                prop_types.insert(prop_name, prop_type);
+                            }
+                            key => {
+                                self.warn(key.span(), "non-identifier prop type key is not extracted - this prop's C# type will fall back to dynamic");
+                            }
                         }
                     }
                 }
@@ -398,15 +774,20 @@ impl MinimactTransformer {
                 for prop in &obj_pat.props {
                     match prop {
                         ObjectPatProp::KeyValue(kv) => {
-                            if let PropName::Ident(ident) = &kv.key {
-                                let name = ident.sym.to_string();
-                                let prop_type = prop_types.get(&name)
-                                    .cloned()
-                                    .unwrap_or_else(|| "dynamic".to_string());
-                                component.props.push(crate::component::Prop {
-                                    name,
-                                    prop_type,
-                                });
+                            match &kv.key {
+                                PropName::Ident(ident) => {
+                                    let name = ident.sym.to_string();
+                                    let prop_type = prop_types.get(&name)
+                                        .cloned()
+                                        .unwrap_or_else(|| "dynamic".to_string());
+                                    component.props.push(crate::component::Prop {
+                                        name,
+                                        prop_type,
+                                    });
+                                }
+                                key => {
+                                    self.warn(key.span(), "non-identifier destructured prop key is not extracted and won't appear on the component");
+                                }
                             }
                         }
                         ObjectPatProp::Assign(assign) => {
@@ -419,7 +800,9 @@ impl MinimactTransformer {
                                 prop_type,
                             });
                         }
-                        ObjectPatProp::Rest(_) => {}
+                        ObjectPatProp::Rest(rest) => {
+                            self.warn(rest.span, "rest element in destructured props is not extracted - its fields won't appear on the component");
+                        }
                     }
                 }
             }
@@ -434,8 +817,13 @@ impl MinimactTransformer {
         }
     }
 
-    /// Convert TypeScript type to C# type
-    fn ts_type_to_csharp(ts_type: &TsType) -> String {
+    /// Convert TypeScript type to C# type. Falls back to `dynamic` for
+    /// anything not representable in C#'s type system (unions,
+    /// intersections, and a handful of rarer `TsType` variants) - each
+    /// fallback records a warning with the offending type's span, since a
+    /// silent `dynamic` there is easy to miss until the generated C# fails
+    /// to compile.
+    fn ts_type_to_csharp(&mut self, ts_type: &TsType) -> String {
         match ts_type {
             TsType::TsKeywordType(kw) => {
                 match kw.kind {
@@ -446,11 +834,14 @@ impl MinimactTransformer {
                     TsKeywordTypeKind::TsNullKeyword => "object".to_string(),
                     TsKeywordTypeKind::TsUndefinedKeyword => "object".to_string(),
                     TsKeywordTypeKind::TsAnyKeyword => "dynamic".to_string(),
-                    _ => "dynamic".to_string(),
+                    _ => {
+                        self.warn(kw.span, "unsupported keyword type falls back to dynamic");
+                        "dynamic".to_string()
+                    }
                 }
             }
             TsType::TsArrayType(arr) => {
-                let elem_type = Self::ts_type_to_csharp(&arr.elem_type);
+                let elem_type = self.ts_type_to_csharp(&arr.elem_type);
                 format!("List<{}>", elem_type)
             }
             TsType::TsTypeRef(type_ref) => {
@@ -460,7 +851,7 @@ impl MinimactTransformer {
                         "Array" => {
                             if let Some(params) = &type_ref.type_params {
                                 if let Some(param) = params.params.get(0) {
-                                    let elem_type = Self::ts_type_to_csharp(param);
+                                    let elem_type = self.ts_type_to_csharp(param);
                                     return format!("List<{}>", elem_type);
                                 }
                             }
@@ -471,20 +862,30 @@ impl MinimactTransformer {
                         _ => name, // Use type name as-is for custom types
                     }
                 } else {
+                    self.warn(type_ref.span, "qualified type name falls back to dynamic");
                     "dynamic".to_string()
                 }
             }
             TsType::TsFnOrConstructorType(_) => "Action".to_string(),
-            TsType::TsUnionOrIntersectionType(_) => "dynamic".to_string(),
+            TsType::TsUnionOrIntersectionType(_) => {
+                self.warn(ts_type.span(), "union/intersection types collapse to dynamic - prop validation won't narrow this type");
+                "dynamic".to_string()
+            }
             TsType::TsLitType(lit) => {
                 match &lit.lit {
                     TsLit::Str(_) => "string".to_string(),
                     TsLit::Number(_) => "int".to_string(),
                     TsLit::Bool(_) => "bool".to_string(),
-                    _ => "dynamic".to_string(),
+                    _ => {
+                        self.warn(lit.span, "unsupported literal type falls back to dynamic");
+                        "dynamic".to_string()
+                    }
                 }
             }
-            _ => "dynamic".to_string(),
+            other => {
+                self.warn(other.span(), "unsupported TypeScript type falls back to dynamic");
+                "dynamic".to_string()
+            }
         }
     }
 
@@ -499,11 +900,16 @@ impl MinimactTransformer {
                 for prop in &obj_pat.props {
                     match prop {
                         ObjectPatProp::KeyValue(kv) => {
-                            if let PropName::Ident(ident) = &kv.key {
-                                component.props.push(crate::component::Prop {
-                                    name: ident.sym.to_string(),
-                                    prop_type: "dynamic".to_string(),
-                                });
+                            match &kv.key {
+                                PropName::Ident(ident) => {
+                                    component.props.push(crate::component::Prop {
+                                        name: ident.sym.to_string(),
+                                        prop_type: "dynamic".to_string(),
+                                    });
+                                }
+                                key => {
+                                    self.warn(key.span(), "non-identifier destructured prop key is not extracted and won't appear on the component");
+                                }
                             }
                         }
                         ObjectPatProp::Assign(assign) => {
@@ -512,7 +918,9 @@ impl MinimactTransformer {
                                 prop_type: "dynamic".to_string(),
                             });
                         }
-                        ObjectPatProp::Rest(_) => {}
+                        ObjectPatProp::Rest(rest) => {
+                            self.warn(rest.span, "rest element in destructured props is not extracted - its fields won't appear on the component");
+                        }
                     }
                 }
             }
@@ -526,9 +934,111 @@ impl MinimactTransformer {
         }
     }
 
-    /// Process a custom hook
-    fn process_custom_hook(&mut self, _func: &mut FnDecl) {
-        // TODO: Implement custom hook processing
+    /// Process a custom hook: free-variable/return data-flow lowering
+    /// modeled on rust-analyzer's `extract_function` - collect the names
+    /// the body declares for itself (nested function/arrow params,
+    /// `let`/`const`, catch params) versus every identifier it references;
+    /// whatever's referenced but neither locally declared nor a module
+    /// global is a real input, independent of what the formal parameter
+    /// list claims. Any `useState`/`useClientState`/`useEffect` found in
+    /// the body is hoisted out, since state can't live in a plain method -
+    /// it gets replayed into each calling component's own hook lists at the
+    /// call site instead (see `extract_custom_hook_call`).
+    fn process_custom_hook(&mut self, func: &mut FnDecl) {
+        let name = func.ident.sym.to_string();
+        let Some(body) = func.function.body.clone() else { return; };
+
+        let module_globals: HashSet<String> = self.external_imports.iter().cloned()
+            .chain(self.top_level_functions.iter().map(|f| f.name.clone()))
+            .collect();
+
+        let mut free_vars = HookFreeVariableCollector::new(&module_globals);
+        body.visit_with(&mut free_vars);
+
+        let mut hoisted_state = Vec::new();
+        let mut hoisted_effects = Vec::new();
+        for stmt in &body.stmts {
+            match stmt {
+                Stmt::Decl(Decl::Var(var_decl)) => {
+                    for decl in &var_decl.decls {
+                        let Some(init) = &decl.init else { continue };
+                        let Expr::Call(call) = &**init else { continue };
+                        let Callee::Expr(callee) = &call.callee else { continue };
+                        let Expr::Ident(ident) = &**callee else { continue };
+
+                        match ident.sym.to_string().as_str() {
+                            "useState" | "useClientState" => {
+                                let mut scratch = Component::new(String::new());
+                                extract_use_state(call, &decl.name, &mut scratch);
+                                hoisted_state.extend(scratch.use_state);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                Stmt::Expr(expr_stmt) => {
+                    if let Expr::Call(call) = &*expr_stmt.expr {
+                        if let Callee::Expr(callee) = &call.callee {
+                            if let Expr::Ident(ident) = &**callee {
+                                if ident.sym.to_string() == "useEffect" {
+                                    let mut scratch = Component::new(String::new());
+                                    extract_use_effect(call, &mut scratch);
+                                    hoisted_effects.extend(scratch.use_effect);
+                                }
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let output = Self::infer_hook_output(&body);
+
+        self.custom_hook_definitions.insert(name.clone(), CustomHookDefinition {
+            name,
+            inputs: free_vars.referenced,
+            output,
+            hoisted_state,
+            hoisted_effects,
+        });
+    }
+
+    /// Inspect a hook's `return` statement to decide how a calling
+    /// component's binding pattern maps onto it - a plain value, or named
+    /// fields from a returned object/array literal.
+    fn infer_hook_output(body: &BlockStmt) -> HookOutput {
+        for stmt in &body.stmts {
+            let Stmt::Return(ret) = stmt else { continue };
+            return match &ret.arg {
+                None => HookOutput::None,
+                Some(expr) => match &**expr {
+                    Expr::Object(obj) => {
+                        let fields = obj.props.iter().filter_map(|prop| match prop {
+                            PropOrSpread::Prop(p) => match &**p {
+                                Prop::Shorthand(ident) => Some(ident.sym.to_string()),
+                                Prop::KeyValue(kv) => match &kv.key {
+                                    PropName::Ident(id) => Some(id.sym.to_string()),
+                                    _ => None,
+                                },
+                                _ => None,
+                            },
+                            PropOrSpread::Spread(_) => None,
+                        }).collect();
+                        HookOutput::Fields(fields)
+                    }
+                    Expr::Array(arr) => {
+                        let fields = arr.elems.iter().filter_map(|elem| {
+                            let elem = elem.as_ref()?;
+                            if let Expr::Ident(ident) = &*elem.expr { Some(ident.sym.to_string()) } else { None }
+                        }).collect();
+                        HookOutput::Fields(fields)
+                    }
+                    _ => HookOutput::Scalar,
+                },
+            };
+        }
+        HookOutput::None
     }
 }
 
@@ -537,23 +1047,56 @@ impl VisitMut for MinimactTransformer {
     fn visit_mut_program(&mut self, program: &mut Program) {
         match program {
             Program::Module(module) => {
-                // First pass: collect top-level functions and imports
+                // Seed the css-prop class-name generator with any classes
+                // the author already wrote by hand.
+                let mut class_collector = ClassNameCollector { used: &mut self.used_class_names };
+                module.visit_mut_with(&mut class_collector);
+
+                // First pass: collect top-level functions and imports, and
+                // analyze custom hook definitions - components are only
+                // processed in the second pass below, so a hook's call
+                // sites can resolve it from `custom_hook_definitions`
+                // regardless of whether the hook or the component using it
+                // was declared first in the source.
                 for item in &mut module.body {
                     match item {
                         // Track imports
                         ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
                             self.process_import(import);
                         }
-                        // Collect helper functions (lowercase)
+                        // Collect helper functions (lowercase) and custom hooks
                         ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl))) => {
                             let name = fn_decl.ident.sym.to_string();
-                            if !Self::is_component_name(&name) {
+                            if Self::is_custom_hook(&name) {
+                                self.process_custom_hook(fn_decl);
+                            } else if !Self::is_component_name(&name) {
                                 self.top_level_functions.push(TopLevelFunction {
                                     name,
                                     node: fn_decl.clone(),
                                 });
                             }
                         }
+                        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => {
+                            match &export.decl {
+                                Decl::Fn(fn_decl) => {
+                                    self.file_exports.named.insert(fn_decl.ident.sym.to_string());
+                                }
+                                Decl::Var(var_decl) => {
+                                    for declarator in &var_decl.decls {
+                                        if let Pat::Ident(ident) = &declarator.name {
+                                            self.file_exports.named.insert(ident.id.sym.to_string());
+                                        }
+                                    }
+                                }
+                                _ => {}
+                            }
+                            if let Decl::Fn(fn_decl) = &mut export.decl {
+                                let name = fn_decl.ident.sym.to_string();
+                                if Self::is_custom_hook(&name) {
+                                    self.process_custom_hook(fn_decl);
+                                }
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -561,10 +1104,11 @@ impl VisitMut for MinimactTransformer {
                 // Second pass: process components with manual iteration
                 for item in &mut module.body {
                     match item {
-                        // Function declarations (components)
+                        // Function declarations (components) - custom hooks
+                        // were already analyzed in the first pass above
                         ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl))) => {
                             let name = fn_decl.ident.sym.to_string();
-                            if Self::is_component_name(&name) || Self::is_custom_hook(&name) {
+                            if Self::is_component_name(&name) {
                                 self.push_parent(ParentContext::FunctionDeclaration(name.clone()));
                                 self.process_component(fn_decl);
                                 self.pop_parent();
@@ -575,7 +1119,7 @@ impl VisitMut for MinimactTransformer {
                             if let Decl::Fn(fn_decl) = &mut export.decl {
                                 let name = fn_decl.ident.sym.to_string();
                                 self.push_parent(ParentContext::ExportNamed(Some(name.clone())));
-                                if Self::is_component_name(&name) || Self::is_custom_hook(&name) {
+                                if Self::is_component_name(&name) {
                                     self.process_component(fn_decl);
                                 }
                                 self.pop_parent();
@@ -594,6 +1138,9 @@ impl VisitMut for MinimactTransformer {
             }
             Program::Script(script) => {
                 // Similar handling for scripts
+                let mut class_collector = ClassNameCollector { used: &mut self.used_class_names };
+                script.visit_mut_with(&mut class_collector);
+
                 for stmt in &mut script.body {
                     match stmt {
                         Stmt::Decl(Decl::Fn(fn_decl)) => {
@@ -651,41 +1198,167 @@ impl MinimactTransformer {
         let input_path = Path::new(&self.input_file_path);
         let output_dir = input_path.parent().unwrap_or(Path::new("."));
 
-        for component in &self.components {
-            // 1. Generate C# file
-            let cs_code = self.generate_csharp_code(component);
-            let cs_file_path = output_dir.join(format!("{}.cs", component.name));
-
-            if let Err(e) = fs::write(&cs_file_path, &cs_code) {
-                eprintln!("[Minimact C#] Failed to write {:?}: {}", cs_file_path, e);
-            } else {
-                println!("[Minimact C#] Generated {:?}", cs_file_path);
+        if !self.diagnostics.is_empty() {
+            let source = fs::read_to_string(input_path).ok();
+            for diagnostic in &self.diagnostics {
+                let severity = match diagnostic.severity {
+                    DiagnosticSeverity::Warning => "warning",
+                    DiagnosticSeverity::Error => "error",
+                };
+                match &source {
+                    Some(source) => {
+                        let (line, column) = resolve_line_col(source, diagnostic.span.lo.0);
+                        eprintln!(
+                            "[Minimact] {}: {} ({}:{}:{})",
+                            severity, diagnostic.message, self.input_file_path, line, column
+                        );
+                    }
+                    None => eprintln!("[Minimact] {}: {} ({})", severity, diagnostic.message, self.input_file_path),
+                }
             }
+        }
 
-            // 2. Generate .templates.json file
-            if !component.templates.is_empty() {
-                let templates_json = self.generate_templates_json(component);
-                let templates_file_path = output_dir.join(format!("{}.templates.json", component.name));
+        // Each component's C#/templates/Handlebars output only reads this
+        // component and the transformer's already-finished analysis state
+        // (`input_file_path`, `external_imports`, ...), so rendering
+        // partitions cleanly across threads - same chunk-per-worker split
+        // `rustscript`'s `testing::run_tests_parallel` uses for independent
+        // snapshot test cases. Each worker returns its log lines instead of
+        // printing them directly, so they can be flushed back out in
+        // original component order once every worker has finished.
+        let worker_count = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+            .min(self.components.len().max(1));
+
+        let log_lines: Vec<Vec<(bool, String)>> = if worker_count <= 1 {
+            self.components.iter().map(|component| self.generate_component_outputs(component, output_dir)).collect()
+        } else {
+            let chunk_size = (self.components.len() + worker_count - 1) / worker_count;
+            let mut results = Vec::with_capacity(self.components.len());
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = self
+                    .components
+                    .chunks(chunk_size.max(1))
+                    .map(|chunk| {
+                        scope.spawn(move || {
+                            chunk.iter().map(|component| self.generate_component_outputs(component, output_dir)).collect::<Vec<_>>()
+                        })
+                    })
+                    .collect();
+                for handle in handles {
+                    if let Ok(chunk_logs) = handle.join() {
+                        results.extend(chunk_logs);
+                    }
+                }
+            });
+            results
+        };
 
-                if let Err(e) = fs::write(&templates_file_path, &templates_json) {
-                    eprintln!("[Minimact Templates] Failed to write {:?}: {}", templates_file_path, e);
+        for component_logs in log_lines {
+            for (is_error, message) in component_logs {
+                if is_error {
+                    eprintln!("{}", message);
                 } else {
-                    println!("[Minimact Templates] Generated {:?}", templates_file_path);
+                    println!("{}", message);
                 }
             }
-
-            // 3. Generate .timeline-templates.json if timeline exists
-            // TODO: Implement when timeline analysis is complete
-
-            // 4. Generate .structural-changes.json for hot reload
-            // This would require comparing with previous state
-            // TODO: Implement structural change detection
         }
 
         // Note: .tsx.keys file generation requires access to original source
         // which is handled differently in SWC vs Babel
     }
 
+    /// Render and write every artifact for one component - C#, templates
+    /// JSON, timeline/structural-changes JSON if applicable, and the
+    /// Handlebars template - returning the log lines instead of printing
+    /// them directly, since this runs on a worker thread in
+    /// `generate_outputs`. The rendering itself is `render_component_output`,
+    /// shared with `transform_source_in_memory`'s filesystem-free path.
+    fn generate_component_outputs(&self, component: &Component, output_dir: &std::path::Path) -> Vec<(bool, String)> {
+        use std::fs;
+
+        let mut logs = Vec::new();
+
+        // Diffed against whatever this component's own last build emitted
+        // here, read back inside `render_component_output` before we
+        // overwrite it below.
+        let structural_changes_path = output_dir.join(format!("{}.structural-changes.json", component.name));
+        let output = self.render_component_output(component, &structural_changes_path);
+
+        // 1. Generate C# file
+        let cs_file_path = output_dir.join(format!("{}.cs", component.name));
+        if let Err(e) = fs::write(&cs_file_path, &output.csharp) {
+            logs.push((true, format!("[Minimact C#] Failed to write {:?}: {}", cs_file_path, e)));
+        } else {
+            logs.push((false, format!("[Minimact C#] Generated {:?}", cs_file_path)));
+        }
+
+        // 2. Generate .templates.json file
+        if let Some(templates_json) = &output.templates_json {
+            let templates_file_path = output_dir.join(format!("{}.templates.json", component.name));
+            if let Err(e) = fs::write(&templates_file_path, templates_json) {
+                logs.push((true, format!("[Minimact Templates] Failed to write {:?}: {}", templates_file_path, e)));
+            } else {
+                logs.push((false, format!("[Minimact Templates] Generated {:?}", templates_file_path)));
+            }
+        }
+
+        // 3. Generate .timeline-templates.json if a `useTimeline` was found
+        if let Some(timeline_json) = &output.timeline_json {
+            let timeline_file_path = output_dir.join(format!("{}.timeline-templates.json", component.name));
+            if let Err(e) = fs::write(&timeline_file_path, timeline_json) {
+                logs.push((true, format!("[Minimact Timeline] Failed to write {:?}: {}", timeline_file_path, e)));
+            } else {
+                logs.push((false, format!("[Minimact Timeline] Generated {:?}", timeline_file_path)));
+            }
+        }
+
+        // 4. Generate .structural-changes.json for hot reload
+        if let Err(e) = fs::write(&structural_changes_path, &output.structural_changes_json) {
+            logs.push((true, format!("[Minimact Structural] Failed to write {:?}: {}", structural_changes_path, e)));
+        } else {
+            logs.push((false, format!("[Minimact Structural] Generated {:?}", structural_changes_path)));
+        }
+
+        // 5. Generate a Handlebars-syntax sibling of .templates.json for
+        // consumers that already have a Handlebars-compatible renderer.
+        let handlebars_file_path = output_dir.join(format!("{}.hbs", component.name));
+        if let Err(e) = fs::write(&handlebars_file_path, &output.handlebars) {
+            logs.push((true, format!("[Minimact Handlebars] Failed to write {:?}: {}", handlebars_file_path, e)));
+        } else {
+            logs.push((false, format!("[Minimact Handlebars] Generated {:?}", handlebars_file_path)));
+        }
+
+        logs
+    }
+
+    /// Render every artifact for one component as in-memory strings,
+    /// without writing anything to disk - the shared core behind the
+    /// file-writing `generate_component_outputs` above and the fully
+    /// in-memory `transform_source_in_memory`. `previous_structural_path`
+    /// is only read (never written) to diff against a prior build for hot
+    /// reload; pass a path that doesn't exist (as `transform_source_in_memory`
+    /// does) to always render a first-mount structural diff.
+    fn render_component_output(&self, component: &Component, previous_structural_path: &std::path::Path) -> ComponentOutput {
+        let templates_json = if !component.templates.is_empty() || !component.styles.is_empty() || !component.handlers.is_empty() {
+            Some(self.generate_templates_json(component))
+        } else {
+            None
+        };
+
+        let timeline_json = component.timeline.as_ref().map(|timeline| self.generate_timeline_json(component, timeline));
+
+        ComponentOutput {
+            name: component.name.clone(),
+            csharp: self.generate_csharp_code(component),
+            templates_json,
+            timeline_json,
+            structural_changes_json: self.generate_structural_changes_json(component, previous_structural_path),
+            handlebars: self.generate_handlebars_template(component),
+        }
+    }
+
     /// Generate C# code for a component
     fn generate_csharp_code(&self, component: &Component) -> String {
         let mut code = String::new();
@@ -693,7 +1366,24 @@ impl MinimactTransformer {
         // Using statements
         code.push_str("using System;\n");
         code.push_str("using System.Collections.Generic;\n");
-        code.push_str("using Minimact;\n\n");
+        code.push_str("using Minimact;\n");
+
+        // Other Minimact components this one renders, as resolved by
+        // `link_component_references`. That pass runs over the components a
+        // finished transform returns, while `generate_outputs` renders them
+        // inside the transform, so there every `resolved_class_name` is
+        // still `None` and no such line is emitted. No `using` is
+        // needed for them - every generated class lives in the same
+        // implicit top-level namespace - so this is just a record of the
+        // dependency for readability.
+        let mut referenced_classes: Vec<&String> =
+            component.component_references.iter().filter_map(|r| r.resolved_class_name.as_ref()).collect();
+        referenced_classes.sort();
+        referenced_classes.dedup();
+        for class_name in referenced_classes {
+            code.push_str(&format!("// renders {}\n", class_name));
+        }
+        code.push('\n');
 
         // Namespace and class
         code.push_str(&format!("public class {} : MinimactComponent\n{{\n", component.name));
@@ -731,8 +1421,8 @@ impl MinimactTransformer {
                 .join(", ");
 
             let async_modifier = if func.is_async { "async " } else { "" };
-            code.push_str(&format!("\n    public {}{}{}({})\n    {{\n        // TODO: Implement\n    }}\n",
-                async_modifier, func.return_type, func.name, params));
+            code.push_str(&format!("\n    public {}{}{}({})\n    {{\n{}    }}\n",
+                async_modifier, func.return_type, func.name, params, func.body));
         }
 
         // Render method
@@ -772,9 +1462,14 @@ impl MinimactTransformer {
 
         // Structural templates
         let structural_templates: Vec<_> = component.structural_templates.iter().map(|st| {
+            let branches: Vec<_> = st.branches.iter().map(|branch| {
+                json!({ "conditionBinding": branch.condition_binding })
+            }).collect();
+
             json!({
                 "type": st.template_type,
-                "conditionBinding": st.condition_binding
+                "conditionBinding": st.condition_binding,
+                "branches": branches
             })
         }).collect();
 
@@ -790,38 +1485,227 @@ impl MinimactTransformer {
 
         // Expression templates
         let expression_templates: Vec<_> = component.expression_templates.iter().map(|et| {
+            let stages: Vec<_> = et.stages.iter().map(|stage| {
+                json!({
+                    "method": stage.method,
+                    "args": stage.args,
+                    "itemVar": stage.item_var,
+                    "projectedBinding": stage.projected_binding
+                })
+            }).collect();
+
             json!({
                 "type": et.template_type,
                 "stateKey": et.state_key,
                 "binding": et.binding,
                 "method": et.method,
-                "args": et.args
+                "args": et.args,
+                "csharp": et.csharp,
+                "stages": stages
+            })
+        }).collect();
+
+        // Style entries (css prop extraction)
+        let styles: Vec<_> = component.styles.iter().map(|style| {
+            json!({
+                "className": style.class_name,
+                "css": style.css,
+                "bindings": style.bindings,
+                "isHostElement": style.is_host_element
             })
         }).collect();
 
+        // Event handler capture analysis
+        let mut handler_map = serde_json::Map::new();
+        for (key, handler) in &component.handlers {
+            handler_map.insert(key.clone(), json!({
+                "path": handler.path,
+                "eventName": handler.event_name,
+                "reads": handler.reads,
+                "writes": handler.writes,
+                "refs": handler.refs
+            }));
+        }
+
         let result = json!({
             "componentName": component.name,
             "templates": template_map,
             "loopTemplates": loop_templates,
             "structuralTemplates": structural_templates,
             "conditionalElementTemplates": conditional_map,
-            "expressionTemplates": expression_templates
+            "expressionTemplates": expression_templates,
+            "styles": styles,
+            "handlers": handler_map
         });
 
         to_string_pretty(&result).unwrap_or_else(|_| "{}".to_string())
     }
 
-    /// Get parent context
-    fn get_parent(&self) -> Option<&ParentContext> {
-        self.parent_stack.last()
-    }
+    /// Generate timeline JSON for a component's `useTimeline` analysis
+    fn generate_timeline_json(&self, component: &Component, timeline: &Timeline) -> String {
+        use serde_json::{json, to_string_pretty};
 
-    /// Push parent context
-    fn push_parent(&mut self, ctx: ParentContext) {
-        self.parent_stack.push(ctx);
-    }
+        let keyframes: Vec<_> = timeline.keyframes.iter().map(|keyframe| {
+            json!({
+                "time": keyframe.time,
+                "state": keyframe.state,
+                "value": keyframe.value
+            })
+        }).collect();
 
-    /// Pop parent context
+        let result = json!({
+            "componentName": component.name,
+            "duration": timeline.duration,
+            "keyframes": keyframes,
+            "stateBindings": timeline.state_bindings.iter().cloned().collect::<Vec<_>>()
+        });
+
+        to_string_pretty(&result).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// Generate `.structural-changes.json` for Fast Refresh: a hook-call
+    /// signature (see `compute_hook_signature`) plus a diff of template
+    /// paths against whatever this same component last wrote to
+    /// `previous_path`. If the hook signature is unchanged, the runtime's
+    /// positional hook state is still valid and only the listed template
+    /// paths need patching; if it changed (a hook was added, removed,
+    /// reordered, or changed type), the component needs a full remount
+    /// instead of an in-place patch.
+    fn generate_structural_changes_json(&self, component: &Component, previous_path: &std::path::Path) -> String {
+        use serde_json::{json, to_string_pretty};
+
+        let hook_signature = compute_hook_signature(component);
+        let template_contents = collect_template_contents(component);
+        let previous = read_previous_structural_state(previous_path);
+
+        let (added_paths, removed_paths, moved_paths, patch_type) = match &previous {
+            Some(previous) => {
+                let (added, removed, moved) = diff_template_paths(&previous.template_contents, &template_contents);
+                let patch_type = if previous.hook_signature == hook_signature {
+                    "state-preserving"
+                } else {
+                    "full-remount"
+                };
+                (added, removed, moved, patch_type)
+            }
+            // Nothing to compare against yet - there's no previous state to
+            // preserve, so this is effectively the component's first mount.
+            None => (Vec::new(), Vec::new(), Vec::new(), "full-remount"),
+        };
+
+        let moved_json: Vec<_> = moved_paths.iter().map(|(from, to)| json!({ "from": from, "to": to })).collect();
+
+        let result = json!({
+            "componentName": component.name,
+            "hookSignature": hook_signature,
+            "patchType": patch_type,
+            "addedPaths": added_paths,
+            "removedPaths": removed_paths,
+            "movedPaths": moved_json,
+            "templateContentHashes": template_contents
+        });
+
+        to_string_pretty(&result).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// Handlebars-syntax sibling of `generate_templates_json`, for consumers
+    /// with an existing Handlebars-compatible renderer instead of the
+    /// proprietary JSON shape. Text/attribute `Template`s nest under their
+    /// JSX element's dotted index path so the output mirrors the original
+    /// element tree. `LoopTemplate`, `StructuralTemplate`, and
+    /// `ExpressionTemplate` don't carry a path in this data model (see their
+    /// doc comments), so they're appended as flat top-level blocks instead
+    /// of being nested into the tree.
+    fn generate_handlebars_template(&self, component: &Component) -> String {
+        let mut out = String::new();
+        out.push_str("{{!-- ");
+        out.push_str(&component.name);
+        out.push_str(" --}}\n");
+
+        let mut nodes: Vec<Vec<usize>> = vec![Vec::new()];
+        for template in component.templates.values() {
+            let path = parse_template_path(&template.path);
+            for n in 0..=path.len() {
+                nodes.push(path[..n].to_vec());
+            }
+        }
+        nodes.sort();
+        nodes.dedup();
+
+        render_handlebars_node(&mut out, component, &nodes, &[]);
+
+        for loop_template in &component.loop_templates {
+            out.push_str("{{#each ");
+            out.push_str(&loop_template.state_key);
+            out.push_str(" as |");
+            out.push_str(&loop_template.item_var);
+            out.push(' ');
+            out.push_str(loop_template.index_var.as_deref().unwrap_or("index"));
+            out.push_str("|}}\n  {{!-- keyed by ");
+            out.push_str(&loop_template.key_expression);
+            out.push_str(" - not positioned, LoopTemplate has no path --}}\n{{/each}}\n");
+        }
+
+        for structural in &component.structural_templates {
+            if structural.template_type == "switch" {
+                for (i, branch) in structural.branches.iter().enumerate() {
+                    out.push_str(if i == 0 { "{{#if " } else { "{{else if " });
+                    out.push_str(&branch.condition_binding);
+                    out.push_str("}}\n  {{!-- switch branch, not positioned - StructuralTemplate has no path --}}\n");
+                }
+                out.push_str("{{else}}\n  {{!-- switch default, not positioned - StructuralTemplate has no path --}}\n{{/if}}\n");
+                continue;
+            }
+
+            out.push_str("{{#if ");
+            out.push_str(&structural.condition_binding);
+            out.push_str("}}\n  {{!-- ");
+            out.push_str(&structural.template_type);
+            out.push_str(" block, not positioned - StructuralTemplate has no path --}}\n{{/if}}\n");
+        }
+
+        let mut conditional_paths: Vec<&String> = component.conditional_element_templates.keys().collect();
+        conditional_paths.sort();
+        for path in conditional_paths {
+            let cet = &component.conditional_element_templates[path];
+            out.push_str("{{#if ");
+            out.push_str(&cet.condition_expression);
+            out.push_str("}}\n  {{!-- element at ");
+            out.push_str(&cet.path);
+            out.push_str(" --}}\n{{/if}}\n");
+        }
+
+        for expr_template in &component.expression_templates {
+            out.push_str("{{");
+            match &expr_template.method {
+                Some(method) => {
+                    out.push_str(method);
+                    out.push(' ');
+                    out.push_str(&expr_template.binding);
+                    for arg in &expr_template.args {
+                        out.push(' ');
+                        out.push_str(arg);
+                    }
+                }
+                None => out.push_str(&expr_template.binding),
+            }
+            out.push_str("}}\n");
+        }
+
+        out
+    }
+
+    /// Get parent context
+    fn get_parent(&self) -> Option<&ParentContext> {
+        self.parent_stack.last()
+    }
+
+    /// Push parent context
+    fn push_parent(&mut self, ctx: ParentContext) {
+        self.parent_stack.push(ctx);
+    }
+
+    /// Pop parent context
     fn pop_parent(&mut self) {
         self.parent_stack.pop();
     }
@@ -830,26 +1714,33 @@ impl MinimactTransformer {
     fn process_import(&mut self, import: &ImportDecl) {
         let source = String::from_utf8_lossy(import.src.value.as_bytes()).to_string();
 
-        // Skip internal imports
-        if source.starts_with("minimact") ||
-           source.starts_with('.') ||
-           source.starts_with('/') ||
-           source.ends_with(".css") {
+        // Skip internal imports and stylesheets - neither ever names a
+        // component or helper this build needs to resolve.
+        if source.starts_with("minimact") || source.ends_with(".css") {
             return;
         }
 
-        // Track external identifiers
+        let is_relative = source.starts_with('.') || source.starts_with('/');
+
         for spec in &import.specifiers {
-            match spec {
-                ImportSpecifier::Default(default) => {
-                    self.external_imports.insert(default.local.sym.to_string());
-                }
+            let (local_name, imported_name) = match spec {
+                ImportSpecifier::Default(default) => (default.local.sym.to_string(), ImportedName::Default),
                 ImportSpecifier::Named(named) => {
-                    self.external_imports.insert(named.local.sym.to_string());
-                }
-                ImportSpecifier::Namespace(ns) => {
-                    self.external_imports.insert(ns.local.sym.to_string());
+                    let local_name = named.local.sym.to_string();
+                    let imported_name = match &named.imported {
+                        Some(ModuleExportName::Ident(ident)) => ImportedName::Named(ident.sym.to_string()),
+                        Some(ModuleExportName::Str(s)) => ImportedName::Named(s.value.to_string()),
+                        None => ImportedName::Named(local_name.clone()),
+                    };
+                    (local_name, imported_name)
                 }
+                ImportSpecifier::Namespace(ns) => (ns.local.sym.to_string(), ImportedName::Namespace),
+            };
+
+            if is_relative {
+                self.relative_imports.insert(local_name, RelativeImport { source: source.clone(), imported_name });
+            } else {
+                self.external_imports.insert(local_name);
             }
         }
     }
@@ -974,7 +1865,7 @@ impl VisitMut for HookExtractor<'_> {
                                 if callee_name.starts_with("use") && callee_name.len() > 3 {
                                     if let Some(c) = callee_name.chars().nth(3) {
                                         if c.is_uppercase() {
-                                            extract_custom_hook_call(call, &var.name, &callee_name, self.component);
+                                            extract_custom_hook_call(call, &var.name, &callee_name, self.component, self.custom_hook_definitions, self.diagnostics);
                                         }
                                     }
                                 }
@@ -1064,6 +1955,8 @@ impl VisitMut for HelperFunctionExtractor<'_> {
             }
         }
 
+        let body_block = fn_decl.function.body.as_ref();
+
         let params: Vec<FunctionParam> = fn_decl.function.params
             .iter()
             .map(|param| {
@@ -1071,18 +1964,26 @@ impl VisitMut for HelperFunctionExtractor<'_> {
                     Pat::Ident(ident) => ident.id.sym.to_string(),
                     _ => "param".to_string(),
                 };
+                let param_type = body_block
+                    .map(|body| infer_param_type(body, &param_name))
+                    .unwrap_or_else(|| "dynamic".to_string());
                 FunctionParam {
                     name: param_name,
-                    param_type: "dynamic".to_string(),
+                    param_type,
                 }
             })
             .collect();
 
+        let body = body_block
+            .map(|body| translate_helper_body(body, &fn_decl.function.params, self.component, self.diagnostics))
+            .unwrap_or_default();
+
         self.component.helper_functions.push(HelperFunction {
             name,
             params,
             return_type: "void".to_string(),
             is_async: fn_decl.function.is_async,
+            body,
         });
 
         // Don't recurse - we don't need nested functions
@@ -1119,26 +2020,32 @@ impl VisitMut for ImportExtractor<'_> {
     fn visit_mut_import_decl(&mut self, import: &mut ImportDecl) {
         let source = String::from_utf8_lossy(import.src.value.as_bytes()).to_string();
 
-        // Skip internal imports
-        if source.starts_with("minimact") ||
-           source.starts_with('.') ||
-           source.starts_with('/') ||
-           source.ends_with(".css") {
+        // Skip internal imports and stylesheets
+        if source.starts_with("minimact") || source.ends_with(".css") {
             return;
         }
 
-        // Track identifiers
+        let is_relative = source.starts_with('.') || source.starts_with('/');
+
         for spec in &import.specifiers {
-            match spec {
-                ImportSpecifier::Default(default) => {
-                    self.external_imports.insert(default.local.sym.to_string());
-                }
+            let (local_name, imported_name) = match spec {
+                ImportSpecifier::Default(default) => (default.local.sym.to_string(), ImportedName::Default),
                 ImportSpecifier::Named(named) => {
-                    self.external_imports.insert(named.local.sym.to_string());
-                }
-                ImportSpecifier::Namespace(ns) => {
-                    self.external_imports.insert(ns.local.sym.to_string());
+                    let local_name = named.local.sym.to_string();
+                    let imported_name = match &named.imported {
+                        Some(ModuleExportName::Ident(ident)) => ImportedName::Named(ident.sym.to_string()),
+                        Some(ModuleExportName::Str(s)) => ImportedName::Named(s.value.to_string()),
+                        None => ImportedName::Named(local_name.clone()),
+                    };
+                    (local_name, imported_name)
                 }
+                ImportSpecifier::Namespace(ns) => (ns.local.sym.to_string(), ImportedName::Namespace),
+            };
+
+            if is_relative {
+                self.relative_imports.insert(local_name, RelativeImport { source: source.clone(), imported_name });
+            } else {
+                self.external_imports.insert(local_name);
             }
         }
     }
@@ -1220,6 +2127,67 @@ impl VisitMut for JSXTemplateExtractor<'_> {
     }
 }
 
+/// ClassNameCollector - seeds the css-prop class-name generator from
+/// existing literal `className` attributes
+impl VisitMut for ClassNameCollector<'_> {
+    fn visit_mut_jsx_attr(&mut self, attr: &mut JSXAttr) {
+        if let JSXAttrName::Ident(name) = &attr.name {
+            if name.sym.to_string() == "className" {
+                if let Some(JSXAttrValue::Str(s)) = &attr.value {
+                    for class in s.value.split_whitespace() {
+                        self.used.insert(class.to_string());
+                    }
+                }
+            }
+        }
+        attr.visit_mut_children_with(self);
+    }
+}
+
+/// HandlerExtractor - closure-capture analysis for JSX event handler attrs
+impl VisitMut for HandlerExtractor<'_> {
+    fn visit_mut_jsx_element(&mut self, jsx: &mut JSXElement) {
+        if let Some(path) = jsx_hex_key(jsx) {
+            for attr in &jsx.opening.attrs {
+                let JSXAttrOrSpread::JSXAttr(jsx_attr) = attr else { continue };
+                let JSXAttrName::Ident(name) = &jsx_attr.name else { continue };
+                let event_name = name.sym.to_string();
+                if !is_event_handler_name(&event_name) {
+                    continue;
+                }
+
+                let Some(JSXAttrValue::JSXExprContainer(container)) = &jsx_attr.value else { continue };
+                let JSXExpr::Expr(expr) = &container.expr else { continue };
+                if !matches!(&**expr, Expr::Arrow(_) | Expr::Fn(_)) {
+                    continue;
+                }
+
+                let mut reads = Vec::new();
+                let mut writes = Vec::new();
+                let mut refs = Vec::new();
+                for capture in analyze_hook_usage(expr, &*self.component) {
+                    match capture.kind {
+                        CaptureKind::Read => reads.push(capture.name),
+                        CaptureKind::Write => writes.push(capture.name),
+                        CaptureKind::Ref => refs.push(capture.name),
+                    }
+                }
+
+                let key = format!("{}@{}", path, event_name);
+                self.component.handlers.insert(key, HandlerInfo {
+                    path: path.clone(),
+                    event_name,
+                    reads,
+                    writes,
+                    refs,
+                });
+            }
+        }
+
+        jsx.visit_mut_children_with(self);
+    }
+}
+
 /// LoopExtractor - extracts .map() patterns for loop templates
 impl VisitMut for LoopExtractor<'_> {
     fn visit_mut_call_expr(&mut self, call: &mut CallExpr) {
@@ -1257,6 +2225,12 @@ impl VisitMut for LoopExtractor<'_> {
 
                                     // Extract key expression from JSX
                                     let key_expression = extract_key_from_jsx(&arrow.body);
+                                    if key_expression.is_none() {
+                                        self.component.warnings.push(format!(
+                                            "loop over `{}` is missing a `key` prop - its renders will not be stably diffed",
+                                            state_key
+                                        ));
+                                    }
 
                                     self.component.loop_templates.push(LoopTemplate {
                                         state_key,
@@ -1278,13 +2252,49 @@ impl VisitMut for LoopExtractor<'_> {
 }
 
 /// StructuralExtractor - extracts conditional rendering patterns
+impl StructuralExtractor<'_> {
+    /// Flatten a chained ternary (`a ? <A/> : b ? <B/> : <C/>`) into an
+    /// ordered ladder of branch conditions, visiting each branch's JSX
+    /// consequent - and the tail's default - for templates nested inside,
+    /// but without re-descending into the alt chain itself, which would
+    /// otherwise double up conditional templates for the same ladder.
+    fn flatten_switch(&mut self, cond: &mut CondExpr, branches: &mut Vec<SwitchBranch>) {
+        branches.push(SwitchBranch {
+            condition_binding: extract_binding_from_expr(&cond.test)
+                .unwrap_or_else(|| generate_expr_string(&cond.test)),
+        });
+        cond.cons.visit_mut_with(self);
+
+        match &mut *cond.alt {
+            // Stop flattening once a branch's consequent isn't JSX - the
+            // chain isn't actually a render ladder past this point.
+            Expr::Cond(next) if is_jsx_expr(&next.cons) => self.flatten_switch(next, branches),
+            other => other.visit_mut_with(self),
+        }
+    }
+}
+
 impl VisitMut for StructuralExtractor<'_> {
     // Ternary conditional: condition ? <A /> : <B />
     fn visit_mut_cond_expr(&mut self, cond: &mut CondExpr) {
-        // Check if consequent or alternate is JSX
         let cons_is_jsx = is_jsx_expr(&cond.cons);
-        let alt_is_jsx = is_jsx_expr(&cond.alt);
 
+        // Chained ternary: `status === "a" ? <A/> : status === "b" ? <B/> :
+        // <C/>` - flatten into a "switch" ladder instead of collapsing to a
+        // single "conditional" that would lose every case but the first.
+        if cons_is_jsx && matches!(&*cond.alt, Expr::Cond(next) if is_jsx_expr(&next.cons)) {
+            let mut branches = Vec::new();
+            self.flatten_switch(cond, &mut branches);
+
+            self.component.structural_templates.push(StructuralTemplate {
+                template_type: "switch".to_string(),
+                condition_binding: String::new(),
+                branches,
+            });
+            return;
+        }
+
+        let alt_is_jsx = is_jsx_expr(&cond.alt);
         if cons_is_jsx || alt_is_jsx {
             let condition_binding = extract_binding_from_expr(&cond.test)
                 .unwrap_or_else(|| generate_expr_string(&cond.test));
@@ -1292,6 +2302,7 @@ impl VisitMut for StructuralExtractor<'_> {
             self.component.structural_templates.push(StructuralTemplate {
                 template_type: "conditional".to_string(),
                 condition_binding,
+                branches: Vec::new(),
             });
         }
 
@@ -1310,6 +2321,7 @@ impl VisitMut for StructuralExtractor<'_> {
                 self.component.structural_templates.push(StructuralTemplate {
                     template_type: "logical".to_string(),
                     condition_binding,
+                    branches: Vec::new(),
                 });
             }
         }
@@ -1331,8 +2343,24 @@ impl VisitMut for ExpressionExtractor<'_> {
                             if let MemberProp::Ident(method) = &member.prop {
                                 let method_name = method.sym.to_string();
 
-                                // Check if this is a supported transform
-                                if is_supported_transform(&method_name) {
+                                // Chained pipeline: items.filter(...).map(...).join(...).
+                                // A real chain has another call as its object; `filter`/
+                                // `map` are also routed here even standalone since
+                                // `is_supported_transform` never covered them.
+                                let is_chained = matches!(&*member.obj, Expr::Call(_));
+                                if is_chained || matches!(method_name.as_str(), "filter" | "map") {
+                                    if let Some((state_key, stages)) = extract_pipeline(expr) {
+                                        self.component.expression_templates.push(ExpressionTemplate {
+                                            template_type: "pipeline".to_string(),
+                                            state_key,
+                                            binding: String::new(),
+                                            method: None,
+                                            args: Vec::new(),
+                                            csharp: None,
+                                            stages,
+                                        });
+                                    }
+                                } else if is_supported_transform(&method_name) {
                                     let binding = extract_binding_from_expr(&member.obj)
                                         .unwrap_or_default();
                                     let state_key = binding.split('.').next()
@@ -1348,6 +2376,8 @@ impl VisitMut for ExpressionExtractor<'_> {
                                         binding,
                                         method: Some(method_name),
                                         args,
+                                        csharp: None,
+                                        stages: Vec::new(),
                                     });
                                 }
                             }
@@ -1355,7 +2385,7 @@ impl VisitMut for ExpressionExtractor<'_> {
                     }
                 }
                 // Binary expression: count * 2 + 1
-                Expr::Bin(bin) => {
+                Expr::Bin(_) => {
                     let bindings = extract_all_bindings(expr);
                     if !bindings.is_empty() {
                         let state_key = bindings[0].split('.').next()
@@ -1367,6 +2397,22 @@ impl VisitMut for ExpressionExtractor<'_> {
                             binding: bindings.join(", "),
                             method: None,
                             args: Vec::new(),
+                            csharp: None,
+                            stages: Vec::new(),
+                        });
+                    } else if let Value::Known(value) = fold_expr(expr) {
+                        // No bindings left once folded (e.g. `1 + 2 * 3`) -
+                        // emit the computed literal directly instead of a
+                        // `binaryExpression` that would just re-derive the
+                        // same constant on every render.
+                        self.component.expression_templates.push(ExpressionTemplate {
+                            template_type: "static".to_string(),
+                            state_key: String::new(),
+                            binding: value.to_csharp_literal(),
+                            method: None,
+                            args: Vec::new(),
+                            csharp: None,
+                            stages: Vec::new(),
                         });
                     }
                 }
@@ -1382,6 +2428,8 @@ impl VisitMut for ExpressionExtractor<'_> {
                             binding,
                             method: None,
                             args: vec![unary.op.to_string()],
+                            csharp: None,
+                            stages: Vec::new(),
                         });
                     }
                 }
@@ -1400,10 +2448,34 @@ impl VisitMut for ExpressionExtractor<'_> {
                                 binding,
                                 method: Some(prop_name),
                                 args: Vec::new(),
+                                csharp: None,
+                                stages: Vec::new(),
                             });
                         }
                     }
                 }
+                // Template literal: `Hello ${user.name}, you have ${count} items`
+                Expr::Tpl(tpl) => {
+                    let mut bindings = Vec::new();
+                    for interpolation in &tpl.exprs {
+                        bindings.extend(extract_all_bindings(interpolation));
+                    }
+
+                    if !bindings.is_empty() {
+                        let state_key = bindings[0].split('.').next()
+                            .unwrap_or(&bindings[0]).to_string();
+
+                        self.component.expression_templates.push(ExpressionTemplate {
+                            template_type: "templateLiteral".to_string(),
+                            state_key,
+                            binding: bindings.join(", "),
+                            method: None,
+                            args: Vec::new(),
+                            csharp: Some(template_literal_to_csharp(tpl)),
+                            stages: Vec::new(),
+                        });
+                    }
+                }
                 _ => {}
             }
         }
@@ -1490,6 +2562,239 @@ fn build_member_path(member: &MemberExpr) -> String {
     parts.join(".")
 }
 
+/// Root identifier of a member chain, looking through parens the way
+/// `build_member_path` looks through nested `Member`s - `user` for
+/// `user.profile.name` as well as `(user).profile.name`. Used by
+/// `infer_effect_dependencies` to resolve a `useEffect` body's member
+/// accesses back to the `state_key` they actually depend on, same as
+/// `extract_all_bindings_inner`'s `Member` arm does for template bindings.
+fn member_chain_root(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Ident(ident) => Some(ident.sym.to_string()),
+        Expr::Member(member) => member_chain_root(&member.obj),
+        Expr::Paren(paren) => member_chain_root(&paren.expr),
+        _ => None,
+    }
+}
+
+// =============================================================================
+// Constant folding
+//
+// Lets `expr_to_csharp` emit a real literal instead of `"null"`, and lets
+// `ExpressionExtractor` skip a `binaryExpression` template, whenever a JSX
+// expression/initial value turns out to be statically computable.
+// =============================================================================
+
+#[derive(Clone, Debug, PartialEq)]
+enum Const {
+    Num(f64),
+    Str(String),
+    Bool(bool),
+    Null,
+}
+
+impl Const {
+    fn to_csharp_literal(&self) -> String {
+        match self {
+            Const::Num(n) => n.to_string(),
+            Const::Str(s) => format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"")),
+            Const::Bool(b) => b.to_string(),
+            Const::Null => "null".to_string(),
+        }
+    }
+
+    /// JS truthiness, as needed to fold `!`, `&&`/`||`, and a folded `?:`
+    /// test.
+    fn truthy(&self) -> bool {
+        match self {
+            Const::Num(n) => *n != 0.0,
+            Const::Str(s) => !s.is_empty(),
+            Const::Bool(b) => *b,
+            Const::Null => false,
+        }
+    }
+
+    /// JS's `String(value)` coercion, for the mixed-type side of a `+`
+    /// string concatenation (`"count: " + 1`).
+    fn to_js_string(&self) -> String {
+        match self {
+            Const::Num(n) => n.to_string(),
+            Const::Str(s) => s.clone(),
+            Const::Bool(b) => b.to_string(),
+            Const::Null => "null".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Known(Const),
+    Unknown,
+}
+
+/// Whether `expr` is safe to fold - no calls, member/index accesses,
+/// assignments, or anything else that could run a side effect or observe
+/// mutable state. `fold_expr` only ever recurses into the handful of
+/// operator kinds below, so this mostly guards against wasting work on an
+/// obviously-unfoldable subtree, but it's the explicit gate that keeps a
+/// future `fold_expr` arm from accidentally treating a call's result as
+/// foldable.
+fn is_pure_expr(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(_) => true,
+        Expr::Paren(paren) => is_pure_expr(&paren.expr),
+        Expr::Unary(unary) => is_pure_expr(&unary.arg),
+        Expr::Bin(bin) => is_pure_expr(&bin.left) && is_pure_expr(&bin.right),
+        Expr::Cond(cond) => is_pure_expr(&cond.test) && is_pure_expr(&cond.cons) && is_pure_expr(&cond.alt),
+        _ => false,
+    }
+}
+
+/// Recursively evaluate a statically-computable expression. Returns
+/// `Value::Unknown` (never a bogus literal) for anything impure, anything
+/// involving a binding whose value isn't known at build time, and any
+/// arithmetic that would produce NaN/Infinity (e.g. division by zero) -
+/// those aren't valid C# literals to embed.
+fn fold_expr(expr: &Expr) -> Value {
+    if !is_pure_expr(expr) {
+        return Value::Unknown;
+    }
+
+    match expr {
+        Expr::Lit(lit) => fold_lit(lit),
+        Expr::Paren(paren) => fold_expr(&paren.expr),
+        Expr::Unary(unary) => fold_unary(unary.op, fold_expr(&unary.arg)),
+        Expr::Bin(bin) => fold_bin(bin.op, &bin.left, &bin.right),
+        Expr::Cond(cond) => match fold_expr(&cond.test) {
+            Value::Known(test) => {
+                if test.truthy() {
+                    fold_expr(&cond.cons)
+                } else {
+                    fold_expr(&cond.alt)
+                }
+            }
+            Value::Unknown => Value::Unknown,
+        },
+        _ => Value::Unknown,
+    }
+}
+
+fn fold_lit(lit: &Lit) -> Value {
+    match lit {
+        Lit::Num(n) => finite_num(n.value),
+        Lit::Str(s) => Value::Known(Const::Str(String::from_utf8_lossy(s.value.as_bytes()).to_string())),
+        Lit::Bool(b) => Value::Known(Const::Bool(b.value)),
+        Lit::Null(_) => Value::Known(Const::Null),
+        _ => Value::Unknown,
+    }
+}
+
+fn fold_unary(op: UnaryOp, arg: Value) -> Value {
+    let Value::Known(arg) = arg else { return Value::Unknown };
+
+    match op {
+        UnaryOp::Minus => match arg {
+            Const::Num(n) => finite_num(-n),
+            _ => Value::Unknown,
+        },
+        UnaryOp::Plus => match arg {
+            Const::Num(n) => finite_num(n),
+            _ => Value::Unknown,
+        },
+        UnaryOp::Bang => Value::Known(Const::Bool(!arg.truthy())),
+        // `typeof null === "object"` is a long-standing JS quirk, not a bug.
+        UnaryOp::TypeOf => Value::Known(Const::Str(
+            match arg {
+                Const::Num(_) => "number",
+                Const::Str(_) => "string",
+                Const::Bool(_) => "boolean",
+                Const::Null => "object",
+            }
+            .to_string(),
+        )),
+        _ => Value::Unknown,
+    }
+}
+
+/// `&&`/`||` are folded here (rather than by recursing through `fold_expr`
+/// on both sides up front) so the short-circuited side is never evaluated,
+/// matching JS's own short-circuit semantics - important once folding
+/// grows to cover impure subexpressions a caller didn't already filter out.
+fn fold_bin(op: BinaryOp, left: &Expr, right: &Expr) -> Value {
+    if matches!(op, BinaryOp::LogicalAnd | BinaryOp::LogicalOr) {
+        return match fold_expr(left) {
+            Value::Known(l) => {
+                let short_circuits = if op == BinaryOp::LogicalAnd { !l.truthy() } else { l.truthy() };
+                if short_circuits {
+                    Value::Known(l)
+                } else {
+                    fold_expr(right)
+                }
+            }
+            Value::Unknown => Value::Unknown,
+        };
+    }
+
+    let (Value::Known(l), Value::Known(r)) = (fold_expr(left), fold_expr(right)) else {
+        return Value::Unknown;
+    };
+
+    match op {
+        BinaryOp::Add => match (&l, &r) {
+            (Const::Num(a), Const::Num(b)) => finite_num(a + b),
+            (Const::Str(a), Const::Str(b)) => Value::Known(Const::Str(format!("{}{}", a, b))),
+            (Const::Str(a), b) => Value::Known(Const::Str(format!("{}{}", a, b.to_js_string()))),
+            (a, Const::Str(b)) => Value::Known(Const::Str(format!("{}{}", a.to_js_string(), b))),
+            _ => Value::Unknown,
+        },
+        BinaryOp::Sub => fold_numeric(l, r, |a, b| a - b),
+        BinaryOp::Mul => fold_numeric(l, r, |a, b| a * b),
+        // Division by zero (or anything else non-finite) falls back to
+        // `Unknown` rather than embedding a C#-invalid `NaN`/`Infinity`.
+        BinaryOp::Div => fold_numeric(l, r, |a, b| a / b),
+        BinaryOp::Mod => fold_numeric(l, r, |a, b| a % b),
+        BinaryOp::Lt => fold_compare(l, r, |o| o == std::cmp::Ordering::Less),
+        BinaryOp::LtEq => fold_compare(l, r, |o| o != std::cmp::Ordering::Greater),
+        BinaryOp::Gt => fold_compare(l, r, |o| o == std::cmp::Ordering::Greater),
+        BinaryOp::GtEq => fold_compare(l, r, |o| o != std::cmp::Ordering::Less),
+        // Loose `==`/`!=` coerce mixed-type operands (`1 == "1"` is true),
+        // so those are only folded when both sides are the same kind of
+        // constant; strict `===`/`!==` compare kind and value directly.
+        BinaryOp::EqEq | BinaryOp::NotEq if std::mem::discriminant(&l) != std::mem::discriminant(&r) => Value::Unknown,
+        BinaryOp::EqEq | BinaryOp::EqEqEq => Value::Known(Const::Bool(l == r)),
+        BinaryOp::NotEq | BinaryOp::NotEqEq => Value::Known(Const::Bool(l != r)),
+        _ => Value::Unknown,
+    }
+}
+
+fn fold_numeric(l: Const, r: Const, op: impl Fn(f64, f64) -> f64) -> Value {
+    match (l, r) {
+        (Const::Num(a), Const::Num(b)) => finite_num(op(a, b)),
+        _ => Value::Unknown,
+    }
+}
+
+fn fold_compare(l: Const, r: Const, matches_ordering: impl Fn(std::cmp::Ordering) -> bool) -> Value {
+    let ordering = match (&l, &r) {
+        (Const::Num(a), Const::Num(b)) => a.partial_cmp(b),
+        (Const::Str(a), Const::Str(b)) => Some(a.cmp(b)),
+        _ => None,
+    };
+
+    match ordering {
+        Some(ordering) => Value::Known(Const::Bool(matches_ordering(ordering))),
+        None => Value::Unknown,
+    }
+}
+
+fn finite_num(n: f64) -> Value {
+    if n.is_finite() {
+        Value::Known(Const::Num(n))
+    } else {
+        Value::Unknown
+    }
+}
+
 fn generate_template_string(expr: &Expr) -> String {
     // Generate a template string representation
     match expr {
@@ -1504,6 +2809,40 @@ fn generate_template_string(expr: &Expr) -> String {
     }
 }
 
+/// Render one template-literal interpolation as C# - a bare identifier or
+/// member path passes through as a property access, anything else falls
+/// back to its folded constant (or `"?"` if it isn't statically known,
+/// where `expr_to_csharp` falls back to `null` for initial values).
+fn interpolation_expr_to_csharp(expr: &Expr) -> String {
+    match expr {
+        Expr::Ident(ident) => ident.sym.to_string(),
+        Expr::Member(member) => build_member_path(member),
+        _ => match fold_expr(expr) {
+            Value::Known(value) => value.to_csharp_literal(),
+            Value::Unknown => "?".to_string(),
+        },
+    }
+}
+
+/// Render a JS template literal as a C# interpolated string - `` `Hello
+/// ${user.name}` `` becomes `$"Hello {user.name}"`. Mirrors
+/// `translate_template`'s quasi-escaping for helper function bodies, but
+/// resolves each interpolation through `interpolation_expr_to_csharp`
+/// instead of the capture-aware `translate_expr`.
+fn template_literal_to_csharp(tpl: &Tpl) -> String {
+    let mut out = String::from("$\"");
+    for (i, quasi) in tpl.quasis.iter().enumerate() {
+        out.push_str(&quasi.raw.replace('\\', "\\\\").replace('"', "\\\"").replace('{', "{{").replace('}', "}}"));
+        if let Some(expr) = tpl.exprs.get(i) {
+            out.push('{');
+            out.push_str(&interpolation_expr_to_csharp(expr));
+            out.push('}');
+        }
+    }
+    out.push('"');
+    out
+}
+
 fn generate_expr_string(expr: &Expr) -> String {
     match expr {
         Expr::Ident(ident) => ident.sym.to_string(),
@@ -1521,100 +2860,906 @@ fn generate_expr_string(expr: &Expr) -> String {
     }
 }
 
-fn is_jsx_expr(expr: &Expr) -> bool {
-    matches!(expr, Expr::JSXElement(_) | Expr::JSXFragment(_))
+/// Resolve a `BytePos` (as produced by loading a single file into a fresh
+/// `SourceMap`, where position 0 is reserved as "unknown") to a 1-based
+/// line/column pair in `source`. Diagnostics only ever carry spans from
+/// this same file, so no cross-file offset bookkeeping is needed.
+fn resolve_line_col(source: &str, byte_pos: u32) -> (usize, usize) {
+    let target = byte_pos.saturating_sub(1) as usize;
+    let mut line = 1;
+    let mut col = 1;
+    let mut pos = 0usize;
+    for ch in source.chars() {
+        if pos >= target {
+            break;
+        }
+        pos += ch.len_utf8();
+        if ch == '\n' {
+            line += 1;
+            col = 1;
+        } else {
+            col += 1;
+        }
+    }
+    (line, col)
 }
 
-fn extract_key_from_jsx(body: &BlockStmtOrExpr) -> Option<String> {
-    // Look for key attribute in JSX element
-    match body {
-        BlockStmtOrExpr::Expr(expr) => {
-            if let Expr::JSXElement(jsx) = &**expr {
-                for attr in &jsx.opening.attrs {
-                    if let JSXAttrOrSpread::JSXAttr(jsx_attr) = attr {
-                        if let JSXAttrName::Ident(name) = &jsx_attr.name {
-                            if name.sym.to_string() == "key" {
-                                if let Some(JSXAttrValue::JSXExprContainer(container)) = &jsx_attr.value {
-                                    if let JSXExpr::Expr(expr) = &container.expr {
-                                        return Some(generate_expr_string(expr));
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        _ => {}
+/// Dotted JSX element path (e.g. `"0.1"`) as produced by the template
+/// extractors, back into the index list it was built from.
+fn parse_template_path(path: &str) -> Vec<usize> {
+    if path.is_empty() {
+        Vec::new()
+    } else {
+        path.split('.').filter_map(|segment| segment.parse::<usize>().ok()).collect()
     }
-    None
 }
 
-fn is_supported_transform(name: &str) -> bool {
-    matches!(name,
-        "toFixed" | "toPrecision" | "toExponential" |
-        "toUpperCase" | "toLowerCase" | "trim" |
-        "substring" | "substr" | "slice" |
-        "length" | "join"
-    )
+fn template_path_string(path: &[usize]) -> String {
+    path.iter().map(|i| i.to_string()).collect::<Vec<_>>().join(".")
 }
 
-fn literal_to_string(expr: &Expr) -> Option<String> {
-    match expr {
-        Expr::Lit(lit) => match lit {
-            Lit::Str(s) => Some(String::from_utf8_lossy(s.value.as_bytes()).to_string()),
-            Lit::Num(n) => Some(n.value.to_string()),
-            Lit::Bool(b) => Some(b.value.to_string()),
-            _ => None,
-        },
-        _ => None,
-    }
+/// `${{name}}`-style placeholders (see `generate_template_string`) become
+/// pure `{{name}}` Handlebars interpolations.
+fn to_handlebars_interpolation(template: &str) -> String {
+    template.replace("${{", "{{")
 }
 
-// =============================================================================
-// Hook Extraction Functions
-// =============================================================================
+/// Render one node of the path tree built in `generate_handlebars_template`:
+/// every `Template` whose own path equals `prefix` (its attribute templates,
+/// keyed `"{path}@{attr}"`, and its own text/expression template, keyed by
+/// `path` directly), then recurse into child paths one level deeper.
+fn render_handlebars_node(out: &mut String, component: &Component, nodes: &[Vec<usize>], prefix: &[usize]) {
+    let prefix_str = template_path_string(prefix);
+    let indent = "  ".repeat(prefix.len());
+    let attr_prefix = format!("{}@", prefix_str);
+
+    let mut keys: Vec<&String> = component
+        .templates
+        .keys()
+        .filter(|key| component.templates[*key].path == prefix_str)
+        .collect();
+    keys.sort();
+
+    for key in keys {
+        let template = &component.templates[key];
+        if let Some(attr_name) = key.strip_prefix(&attr_prefix) {
+            out.push_str(&indent);
+            out.push_str("{{!-- attribute: ");
+            out.push_str(attr_name);
+            out.push_str(" --}}\n");
+        }
+        out.push_str(&indent);
+        out.push_str(&to_handlebars_interpolation(&template.template));
+        out.push('\n');
+    }
 
-fn extract_use_state(call: &CallExpr, binding: &Pat, component: &mut Component) {
-    if let Pat::Array(arr) = binding {
-        let var_name = arr.elems.get(0)
-            .and_then(|e| e.as_ref())
-            .and_then(|p| if let Pat::Ident(id) = p { Some(id.id.sym.to_string()) } else { None })
-            .unwrap_or_default();
+    let mut children: Vec<&Vec<usize>> =
+        nodes.iter().filter(|path| path.len() == prefix.len() + 1 && path.starts_with(prefix)).collect();
+    children.sort();
 
-        let setter_name = arr.elems.get(1)
-            .and_then(|e| e.as_ref())
+    for child in children {
+        render_handlebars_node(out, component, nodes, child);
+    }
+}
+
+fn is_jsx_expr(expr: &Expr) -> bool {
+    matches!(expr, Expr::JSXElement(_) | Expr::JSXFragment(_))
+}
+
+/// Host JSX elements (`div`, `my-element`) start lowercase and use only
+/// lowercase letters, digits, and hyphen-separated lowercase segments;
+/// anything else (`Button`) is a component reference. Hand-rolled since no
+/// `regex` crate is linked into this crate.
+fn is_host_tag_name(name: &str) -> bool {
+    name.split('-').all(is_lowercase_segment) && name.split('-').next().is_some_and(|s| !s.is_empty())
+}
+
+fn is_lowercase_segment(segment: &str) -> bool {
+    let mut chars = segment.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+}
+
+/// `MyButton` -> `my-button`, for deriving a css-prop class-name base from a
+/// component tag name.
+fn to_kebab_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i != 0 {
+                out.push('-');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Event handler attrs (`onClick`, `onChange`) follow the same
+/// `on` + uppercase-letter shape hook names use for `use` - reuse that
+/// convention rather than a fixed handler-name allowlist.
+fn is_event_handler_name(name: &str) -> bool {
+    name.starts_with("on") && name.len() > 2 && name.chars().nth(2).map(|c| c.is_uppercase()).unwrap_or(false)
+}
+
+/// Recover the hex path `assign_hex_paths_to_jsx` stamped onto an element
+/// (or the author's own explicit `key`, if they wrote one instead), so
+/// later passes can key their own per-element output against the same
+/// identity without re-deriving a path.
+fn jsx_hex_key(jsx: &JSXElement) -> Option<String> {
+    jsx.opening.attrs.iter().find_map(|attr| {
+        let JSXAttrOrSpread::JSXAttr(jsx_attr) = attr else { return None };
+        let JSXAttrName::Ident(name) = &jsx_attr.name else { return None };
+        let name = name.sym.to_string();
+        let Some(JSXAttrValue::Str(value)) = &jsx_attr.value else { return None };
+
+        if name == "key" || (name.chars().all(|c| c.is_ascii_hexdigit()) && value.value.to_string() == name) {
+            Some(value.value.to_string())
+        } else {
+            None
+        }
+    })
+}
+
+/// FNV-1a over raw bytes - deterministic across processes and runs (unlike
+/// `std`'s default hasher, which is randomized per-process), so its output
+/// can be persisted and compared against a later build's.
+fn fnv1a_hash(input: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in input.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// Build a stable digest of every hook call a component makes, ordered the
+/// way `Component`'s own fields group them (state hooks, then effect/ref,
+/// content, UI state, pub/sub, task scheduling, server communication, MVC,
+/// optimization, then custom hooks). A hook added, removed, reordered, or
+/// changing its declared type changes the digest; renaming a `useState`
+/// variable does not, since the runtime's positional hook state doesn't
+/// care about the name either.
+fn compute_hook_signature(component: &Component) -> String {
+    let mut descriptor = String::new();
+
+    for state in &component.use_state {
+        descriptor.push_str(&format!("use_state:{}|", state.state_type));
+    }
+    for state in &component.use_client_state {
+        descriptor.push_str(&format!("use_client_state:{}|", state.state_type));
+    }
+    for state in &component.use_protected_state {
+        descriptor.push_str(&format!("use_protected_state:{}|", state.state_type));
+    }
+    for _ in &component.use_state_x {
+        descriptor.push_str("use_state_x|");
+    }
+    for _ in &component.use_effect {
+        descriptor.push_str("use_effect|");
+    }
+    for _ in &component.use_ref {
+        descriptor.push_str("use_ref|");
+    }
+    for _ in &component.use_markdown {
+        descriptor.push_str("use_markdown|");
+    }
+    for _ in &component.use_razor_markdown {
+        descriptor.push_str("use_razor_markdown|");
+    }
+    if component.use_template.is_some() {
+        descriptor.push_str("use_template|");
+    }
+    for _ in &component.use_validation {
+        descriptor.push_str("use_validation|");
+    }
+    for _ in &component.use_modal {
+        descriptor.push_str("use_modal|");
+    }
+    for _ in &component.use_toggle {
+        descriptor.push_str("use_toggle|");
+    }
+    for _ in &component.use_dropdown {
+        descriptor.push_str("use_dropdown|");
+    }
+    for _ in &component.use_pub {
+        descriptor.push_str("use_pub|");
+    }
+    for _ in &component.use_sub {
+        descriptor.push_str("use_sub|");
+    }
+    for _ in &component.use_micro_task {
+        descriptor.push_str("use_micro_task|");
+    }
+    for _ in &component.use_macro_task {
+        descriptor.push_str("use_macro_task|");
+    }
+    for _ in &component.use_signalr {
+        descriptor.push_str("use_signalr|");
+    }
+    for _ in &component.use_server_task {
+        descriptor.push_str("use_server_task|");
+    }
+    for _ in &component.paginated_tasks {
+        descriptor.push_str("paginated_task|");
+    }
+    for _ in &component.use_mvc_state {
+        descriptor.push_str("use_mvc_state|");
+    }
+    for _ in &component.use_mvc_view_model {
+        descriptor.push_str("use_mvc_view_model|");
+    }
+    for _ in &component.use_predict_hint {
+        descriptor.push_str("use_predict_hint|");
+    }
+    for custom_hook in &component.custom_hooks {
+        descriptor.push_str(&format!("custom:{}|", custom_hook.hook_name));
+    }
+
+    format!("{:016x}", fnv1a_hash(&descriptor))
+}
+
+/// Map every JSX template path this component owns to a content digest -
+/// `JSXTemplateExtractor`'s text/attribute templates and
+/// `StructuralExtractor`'s conditional element templates, the only two
+/// template kinds that carry a `path` field. `LoopTemplate` has no path of
+/// its own (it's keyed by `state_key`, not by position in the tree) so
+/// `.map()`-generated nodes aren't represented here yet.
+fn collect_template_contents(component: &Component) -> HashMap<String, String> {
+    let mut contents = HashMap::new();
+
+    for template in component.templates.values() {
+        contents.insert(template.path.clone(), format!("{:016x}", fnv1a_hash(&template.template)));
+    }
+    for cet in component.conditional_element_templates.values() {
+        contents.insert(cet.path.clone(), format!("{:016x}", fnv1a_hash(&cet.condition_expression)));
+    }
+
+    contents
+}
+
+/// Diff a previous build's template paths against this build's. A path
+/// present in both is unchanged. A path that disappeared from one spot and
+/// an otherwise-new path with the same content digest are treated as the
+/// same node having moved, rather than as an unrelated remove + add.
+fn diff_template_paths(
+    previous: &HashMap<String, String>,
+    current: &HashMap<String, String>,
+) -> (Vec<String>, Vec<String>, Vec<(String, String)>) {
+    let mut added: Vec<String> = current.keys().filter(|path| !previous.contains_key(*path)).cloned().collect();
+    let mut removed: Vec<String> = previous.keys().filter(|path| !current.contains_key(*path)).cloned().collect();
+
+    let mut moved = Vec::new();
+    removed.retain(|old_path| {
+        let old_hash = &previous[old_path];
+        match added.iter().position(|new_path| current[new_path] == *old_hash) {
+            Some(index) => {
+                moved.push((old_path.clone(), added.remove(index)));
+                false
+            }
+            None => true,
+        }
+    });
+
+    added.sort();
+    removed.sort();
+    moved.sort();
+    (added, removed, moved)
+}
+
+/// A previous run's own `.structural-changes.json`, read back before it
+/// gets overwritten - there's no separate sidecar file for "last build
+/// state", the emitted artifact doubles as its own baseline.
+struct PreviousStructuralState {
+    hook_signature: String,
+    template_contents: HashMap<String, String>,
+}
+
+fn read_previous_structural_state(path: &std::path::Path) -> Option<PreviousStructuralState> {
+    let raw = std::fs::read_to_string(path).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&raw).ok()?;
+
+    let hook_signature = json.get("hookSignature")?.as_str()?.to_string();
+    let mut template_contents = HashMap::new();
+    for (path, hash) in json.get("templateContentHashes")?.as_object()? {
+        if let Some(hash) = hash.as_str() {
+            template_contents.insert(path.clone(), hash.to_string());
+        }
+    }
+
+    Some(PreviousStructuralState { hook_signature, template_contents })
+}
+
+fn extract_key_from_jsx(body: &BlockStmtOrExpr) -> Option<String> {
+    match body {
+        BlockStmtOrExpr::Expr(expr) => extract_key_from_expr(expr),
+        // Block-bodied arrow: `(item, i) => { const x = ...; return <li
+        // key={item.id}>...</li>; }` - the key lives on whatever the tail
+        // `return` produces, not on the arrow body itself.
+        BlockStmtOrExpr::BlockStmt(block) => block.stmts.iter().rev().find_map(|stmt| match stmt {
+            Stmt::Return(ReturnStmt { arg: Some(arg), .. }) => extract_key_from_expr(arg),
+            _ => None,
+        }),
+    }
+}
+
+fn extract_key_from_expr(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::JSXElement(jsx) => key_attr_value(&jsx.opening.attrs),
+        // A fragment has no opening tag of its own to carry a `key` - fall
+        // back to its first element child's, since that's what the loop
+        // actually keys its render on in practice.
+        Expr::JSXFragment(fragment) => fragment.children.iter().find_map(|child| match child {
+            JSXElementChild::JSXElement(jsx) => key_attr_value(&jsx.opening.attrs),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+fn key_attr_value(attrs: &[JSXAttrOrSpread]) -> Option<String> {
+    for attr in attrs {
+        if let JSXAttrOrSpread::JSXAttr(jsx_attr) = attr {
+            if let JSXAttrName::Ident(name) = &jsx_attr.name {
+                if name.sym.to_string() == "key" {
+                    if let Some(JSXAttrValue::JSXExprContainer(container)) = &jsx_attr.value {
+                        if let JSXExpr::Expr(expr) = &container.expr {
+                            return Some(generate_expr_string(expr));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+fn is_supported_transform(name: &str) -> bool {
+    matches!(name,
+        "toFixed" | "toPrecision" | "toExponential" |
+        "toUpperCase" | "toLowerCase" | "trim" |
+        "substring" | "substr" | "slice" |
+        "length" | "join"
+    )
+}
+
+fn is_pipeline_stage_method(name: &str) -> bool {
+    matches!(name, "filter" | "map" | "join" | "slice" | "reverse" | "sort")
+}
+
+/// Walk a chained method-call pipeline rooted at a single binding -
+/// `items.filter(x => x.active).map(x => x.name).join(", ")` - collecting
+/// each stage in source (left-to-right) order. Returns `None` if any stage
+/// isn't a recognized pipeline method or the chain doesn't bottom out at a
+/// single `state_key` binding.
+fn extract_pipeline(expr: &Expr) -> Option<(String, Vec<PipelineStage>)> {
+    let mut stages = Vec::new();
+    let mut current = expr;
+
+    while let Expr::Call(call) = current {
+        let Callee::Expr(callee) = &call.callee else { return None };
+        let Expr::Member(member) = &**callee else { return None };
+        let MemberProp::Ident(prop) = &member.prop else { return None };
+        let method = prop.sym.to_string();
+        if !is_pipeline_stage_method(&method) {
+            return None;
+        }
+
+        let (item_var, projected_binding) = match call.args.get(0).map(|arg| &*arg.expr) {
+            Some(Expr::Arrow(arrow)) => {
+                let item_var = arrow.params.get(0).and_then(|p| {
+                    if let Pat::Ident(id) = p {
+                        Some(id.id.sym.to_string())
+                    } else {
+                        None
+                    }
+                });
+
+                let projected_binding = match &*arrow.body {
+                    BlockStmtOrExpr::Expr(body_expr) => extract_binding_from_expr(body_expr),
+                    BlockStmtOrExpr::BlockStmt(_) => None,
+                };
+
+                (item_var, projected_binding)
+            }
+            _ => (None, None),
+        };
+
+        let args: Vec<String> = call.args.iter()
+            .filter_map(|arg| literal_to_string(&arg.expr))
+            .collect();
+
+        stages.push(PipelineStage { method, args, item_var, projected_binding });
+        current = &member.obj;
+    }
+
+    if stages.is_empty() {
+        return None;
+    }
+
+    stages.reverse();
+    let state_key = extract_binding_from_expr(current)?;
+    Some((state_key, stages))
+}
+
+fn literal_to_string(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(lit) => match lit {
+            Lit::Str(s) => Some(String::from_utf8_lossy(s.value.as_bytes()).to_string()),
+            Lit::Num(n) => Some(n.value.to_string()),
+            Lit::Bool(b) => Some(b.value.to_string()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// =============================================================================
+// Helper Function Body Translation
+// =============================================================================
+
+/// Record every name a pattern introduces, including destructured params
+/// (`{ a, b: [c] }`, `...rest`) - same shape as the `bind_pat` helpers the
+/// other scope-tracking visitors in this file use, but freestanding since
+/// `LocalNameCollector` doesn't need a scope stack.
+fn bind_pat_names(pat: &Pat, names: &mut HashSet<String>) {
+    match pat {
+        Pat::Ident(ident) => {
+            names.insert(ident.id.sym.to_string());
+        }
+        Pat::Array(arr) => {
+            for elem in arr.elems.iter().flatten() {
+                bind_pat_names(elem, names);
+            }
+        }
+        Pat::Object(obj) => {
+            for prop in &obj.props {
+                match prop {
+                    ObjectPatProp::KeyValue(kv) => bind_pat_names(&kv.value, names),
+                    ObjectPatProp::Assign(assign) => {
+                        names.insert(assign.key.sym.to_string());
+                    }
+                    ObjectPatProp::Rest(rest) => bind_pat_names(&rest.arg, names),
+                }
+            }
+        }
+        Pat::Rest(rest) => bind_pat_names(&rest.arg, names),
+        Pat::Assign(assign) => bind_pat_names(&assign.left, names),
+        Pat::Invalid(_) | Pat::Expr(_) => {}
+    }
+}
+
+/// Every name declared anywhere inside a helper function's own body (plus
+/// its params) - flat, not scope-nested, since all `translate_helper_body`
+/// needs is "does this identifier belong to the function itself" vs "does
+/// it resolve to the enclosing component".
+struct LocalNameCollector {
+    names: HashSet<String>,
+}
+
+impl Visit for LocalNameCollector {
+    fn visit_var_declarator(&mut self, decl: &VarDeclarator) {
+        bind_pat_names(&decl.name, &mut self.names);
+        decl.visit_children_with(self);
+    }
+
+    fn visit_catch_clause(&mut self, catch: &CatchClause) {
+        if let Some(param) = &catch.param {
+            bind_pat_names(param, &mut self.names);
+        }
+        catch.visit_children_with(self);
+    }
+}
+
+fn collect_local_names(body: &BlockStmt, params: &[Param]) -> HashSet<String> {
+    let mut names = HashSet::new();
+    for param in params {
+        bind_pat_names(&param.pat, &mut names);
+    }
+
+    let mut collector = LocalNameCollector { names };
+    body.visit_with(&mut collector);
+    collector.names
+}
+
+/// Infer a helper-function parameter's C# type from how its body uses it -
+/// the one pattern worth special-casing is a DOM/synthetic event object
+/// only ever accessed as `param.target.value`/`param.target.checked`.
+/// Everything else still defaults to `dynamic`.
+fn infer_param_type(body: &BlockStmt, param_name: &str) -> String {
+    let mut finder = EventParamUsageFinder { param_name: param_name.to_string(), looks_like_event: false };
+    body.visit_with(&mut finder);
+
+    if finder.looks_like_event {
+        "MinimactEventArgs".to_string()
+    } else {
+        "dynamic".to_string()
+    }
+}
+
+struct EventParamUsageFinder {
+    param_name: String,
+    looks_like_event: bool,
+}
+
+impl Visit for EventParamUsageFinder {
+    fn visit_member_expr(&mut self, member: &MemberExpr) {
+        if let MemberProp::Ident(outer_prop) = &member.prop {
+            let accesses_value = matches!(outer_prop.sym.to_string().as_str(), "value" | "checked");
+            if let (true, Expr::Member(inner)) = (accesses_value, &*member.obj) {
+                if let (MemberProp::Ident(inner_prop), Expr::Ident(inner_obj)) = (&inner.prop, &*inner.obj) {
+                    if inner_prop.sym == *"target" && inner_obj.sym.to_string() == self.param_name {
+                        self.looks_like_event = true;
+                    }
+                }
+            }
+        }
+        member.visit_children_with(self);
+    }
+}
+
+/// Name-resolution context for translating one helper function's body to
+/// C#: anything declared inside the function itself (params, `let`/
+/// `const`/`var`) stays a bare identifier; anything else that resolves to a
+/// field the enclosing component already exposes (`use_state`, `use_ref`,
+/// `props`, `local_variables`) becomes `this.<name>` field/property access,
+/// mirroring how `generate_csharp_code` names those members.
+struct HelperBodyCtx<'a> {
+    component: &'a Component,
+    locals: HashSet<String>,
+    diagnostics: &'a mut Vec<Diagnostic>,
+}
+
+impl HelperBodyCtx<'_> {
+    fn resolve_ident(&self, name: &str) -> String {
+        if self.locals.contains(name) {
+            return name.to_string();
+        }
+
+        let is_captured = self.component.use_state.iter().any(|s| s.var_name == name)
+            || self.component.use_ref.iter().any(|r| r.name == name)
+            || self.component.props.iter().any(|p| p.name == name)
+            || self.component.local_variables.iter().any(|v| v.name == name);
+
+        if is_captured {
+            format!("this.{}", name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    /// The state variable a `useState` setter assigns, if `name` is one.
+    fn setter_target(&self, name: &str) -> Option<String> {
+        self.component
+            .use_state
+            .iter()
+            .find(|s| s.setter_name.as_deref() == Some(name))
+            .map(|s| s.var_name.clone())
+    }
+}
+
+fn translate_helper_body(
+    body: &BlockStmt,
+    params: &[Param],
+    component: &Component,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> String {
+    let locals = collect_local_names(body, params);
+    let mut ctx = HelperBodyCtx { component, locals, diagnostics };
+    translate_block(body, &mut ctx, 2)
+}
+
+fn translate_block(block: &BlockStmt, ctx: &mut HelperBodyCtx, indent: usize) -> String {
+    let mut out = String::new();
+    for stmt in &block.stmts {
+        out.push_str(&translate_stmt(stmt, ctx, indent));
+    }
+    out
+}
+
+/// Translate a single statement to one or more indented C# lines, each
+/// ending in its own newline, so callers can just concatenate the results.
+fn translate_stmt(stmt: &Stmt, ctx: &mut HelperBodyCtx, indent: usize) -> String {
+    let pad = "    ".repeat(indent);
+
+    match stmt {
+        Stmt::Expr(expr_stmt) => format!("{}{};\n", pad, translate_expr(&expr_stmt.expr, ctx)),
+        Stmt::Decl(Decl::Var(var_decl)) => {
+            let mut out = String::new();
+            for decl in &var_decl.decls {
+                match &decl.name {
+                    Pat::Ident(ident) => {
+                        let value = decl.init.as_deref()
+                            .map(|expr| translate_expr(expr, ctx))
+                            .unwrap_or_else(|| "null".to_string());
+                        out.push_str(&format!("{}var {} = {};\n", pad, ident.id.sym, value));
+                    }
+                    other => {
+                        ctx.diagnostics.push(Diagnostic {
+                            span: other.span(),
+                            severity: DiagnosticSeverity::Warning,
+                            message: "destructured local declarations in helper functions aren't translated".to_string(),
+                        });
+                        out.push_str(&format!("{}// unsupported destructured declaration\n", pad));
+                    }
+                }
+            }
+            out
+        }
+        Stmt::Return(ret) => match &ret.arg {
+            Some(expr) => format!("{}return {};\n", pad, translate_expr(expr, ctx)),
+            None => format!("{}return;\n", pad),
+        },
+        Stmt::If(if_stmt) => {
+            let mut out = format!("{}if ({})\n{}{{\n", pad, translate_expr(&if_stmt.test, ctx), pad);
+            out.push_str(&translate_stmt_as_block(&if_stmt.cons, ctx, indent + 1));
+            out.push_str(&format!("{}}}\n", pad));
+            if let Some(alt) = &if_stmt.alt {
+                out.push_str(&format!("{}else\n{}{{\n", pad, pad));
+                out.push_str(&translate_stmt_as_block(alt, ctx, indent + 1));
+                out.push_str(&format!("{}}}\n", pad));
+            }
+            out
+        }
+        Stmt::Block(block) => {
+            let mut out = format!("{}{{\n", pad);
+            out.push_str(&translate_block(block, ctx, indent + 1));
+            out.push_str(&format!("{}}}\n", pad));
+            out
+        }
+        Stmt::Empty(_) => String::new(),
+        other => {
+            ctx.diagnostics.push(Diagnostic {
+                span: other.span(),
+                severity: DiagnosticSeverity::Warning,
+                message: "unsupported statement kind in helper function body falls back to a comment".to_string(),
+            });
+            format!("{}// unsupported statement\n", pad)
+        }
+    }
+}
+
+fn translate_stmt_as_block(stmt: &Stmt, ctx: &mut HelperBodyCtx, indent: usize) -> String {
+    match stmt {
+        Stmt::Block(block) => translate_block(block, ctx, indent),
+        other => translate_stmt(other, ctx, indent),
+    }
+}
+
+fn translate_expr(expr: &Expr, ctx: &mut HelperBodyCtx) -> String {
+    match expr {
+        Expr::Ident(ident) => ctx.resolve_ident(&ident.sym.to_string()),
+        Expr::This(_) => "this".to_string(),
+        Expr::Lit(lit) => translate_lit(lit),
+        Expr::Paren(paren) => format!("({})", translate_expr(&paren.expr, ctx)),
+        Expr::Bin(bin) => format!(
+            "{} {} {}",
+            translate_expr(&bin.left, ctx),
+            translate_binary_op(bin.op),
+            translate_expr(&bin.right, ctx)
+        ),
+        Expr::Unary(unary) => match unary.op {
+            UnaryOp::Bang => format!("!{}", translate_expr(&unary.arg, ctx)),
+            UnaryOp::Minus => format!("-{}", translate_expr(&unary.arg, ctx)),
+            UnaryOp::Plus => format!("+{}", translate_expr(&unary.arg, ctx)),
+            _ => {
+                ctx.diagnostics.push(Diagnostic {
+                    span: unary.span(),
+                    severity: DiagnosticSeverity::Warning,
+                    message: format!("unsupported unary operator `{:?}` falls back to dynamic null", unary.op),
+                });
+                "null".to_string()
+            }
+        },
+        Expr::Update(update) => {
+            let op = if update.op == UpdateOp::PlusPlus { "++" } else { "--" };
+            let operand = translate_expr(&update.arg, ctx);
+            if update.prefix {
+                format!("{}{}", op, operand)
+            } else {
+                format!("{}{}", operand, op)
+            }
+        }
+        Expr::Assign(assign) => translate_assign(assign, ctx),
+        Expr::Cond(cond) => format!(
+            "{} ? {} : {}",
+            translate_expr(&cond.test, ctx),
+            translate_expr(&cond.cons, ctx),
+            translate_expr(&cond.alt, ctx)
+        ),
+        Expr::Member(member) => translate_member(member, ctx),
+        Expr::Call(call) => translate_call(call, ctx),
+        Expr::Tpl(tpl) => translate_template(tpl, ctx),
+        other => {
+            ctx.diagnostics.push(Diagnostic {
+                span: other.span(),
+                severity: DiagnosticSeverity::Warning,
+                message: "unsupported expression kind in helper function body falls back to dynamic null".to_string(),
+            });
+            "null".to_string()
+        }
+    }
+}
+
+fn translate_lit(lit: &Lit) -> String {
+    match lit {
+        Lit::Str(s) => format!("\"{}\"", escape_csharp_string(&s.value)),
+        Lit::Num(n) => n.value.to_string(),
+        Lit::Bool(b) => b.value.to_string(),
+        _ => "null".to_string(),
+    }
+}
+
+fn translate_binary_op(op: BinaryOp) -> &'static str {
+    match op {
+        BinaryOp::EqEq | BinaryOp::EqEqEq => "==",
+        BinaryOp::NotEq | BinaryOp::NotEqEq => "!=",
+        BinaryOp::Lt => "<",
+        BinaryOp::LtEq => "<=",
+        BinaryOp::Gt => ">",
+        BinaryOp::GtEq => ">=",
+        BinaryOp::Add => "+",
+        BinaryOp::Sub => "-",
+        BinaryOp::Mul => "*",
+        BinaryOp::Div => "/",
+        BinaryOp::Mod => "%",
+        BinaryOp::LogicalAnd => "&&",
+        BinaryOp::LogicalOr => "||",
+        BinaryOp::NullishCoalescing => "??",
+        _ => "/* unsupported operator */",
+    }
+}
+
+fn translate_assign(assign: &AssignExpr, ctx: &mut HelperBodyCtx) -> String {
+    let target = match &assign.left {
+        PatOrExpr::Pat(pat) => match &**pat {
+            Pat::Ident(ident) => ctx.resolve_ident(&ident.id.sym.to_string()),
+            other => {
+                ctx.diagnostics.push(Diagnostic {
+                    span: other.span(),
+                    severity: DiagnosticSeverity::Warning,
+                    message: "destructuring assignment targets aren't translated".to_string(),
+                });
+                "_".to_string()
+            }
+        },
+        PatOrExpr::Expr(expr) => translate_expr(expr, ctx),
+    };
+
+    let op = match assign.op {
+        AssignOp::Assign => "=",
+        AssignOp::AddAssign => "+=",
+        AssignOp::SubAssign => "-=",
+        AssignOp::MulAssign => "*=",
+        AssignOp::DivAssign => "/=",
+        AssignOp::ModAssign => "%=",
+        _ => "=",
+    };
+
+    format!("{} {} {}", target, op, translate_expr(&assign.right, ctx))
+}
+
+fn translate_member(member: &MemberExpr, ctx: &mut HelperBodyCtx) -> String {
+    let obj = translate_expr(&member.obj, ctx);
+    match &member.prop {
+        MemberProp::Ident(ident) => format!("{}.{}", obj, ident.sym),
+        MemberProp::Computed(computed) => format!("{}[{}]", obj, translate_expr(&computed.expr, ctx)),
+        MemberProp::PrivateName(name) => format!("{}.{}", obj, name.name),
+    }
+}
+
+/// A call to a known `useState` setter becomes a property assignment
+/// (`this.count = value`) instead of a method call, since that's what
+/// actually mutates state in the generated C# - the property's setter
+/// already calls `StateHasChanged()`, so no separate signal is needed here.
+fn translate_call(call: &CallExpr, ctx: &mut HelperBodyCtx) -> String {
+    if let Callee::Expr(callee) = &call.callee {
+        if let Expr::Ident(ident) = &**callee {
+            let name = ident.sym.to_string();
+            if let (Some(state_name), [arg]) = (ctx.setter_target(&name), call.args.as_slice()) {
+                let value = translate_expr(&arg.expr, ctx);
+                return format!("this.{} = {}", state_name, value);
+            }
+        }
+    }
+
+    let callee = match &call.callee {
+        Callee::Expr(expr) => translate_expr(expr, ctx),
+        _ => {
+            ctx.diagnostics.push(Diagnostic {
+                span: call.span,
+                severity: DiagnosticSeverity::Warning,
+                message: "`super(...)`/dynamic import calls aren't translated".to_string(),
+            });
+            "/* unsupported callee */".to_string()
+        }
+    };
+    let args: Vec<String> = call.args.iter().map(|arg| translate_expr(&arg.expr, ctx)).collect();
+    format!("{}({})", callee, args.join(", "))
+}
+
+fn translate_template(tpl: &Tpl, ctx: &mut HelperBodyCtx) -> String {
+    let mut out = String::from("$\"");
+    for (i, quasi) in tpl.quasis.iter().enumerate() {
+        out.push_str(&quasi.raw.replace('\\', "\\\\").replace('"', "\\\"").replace('{', "{{").replace('}', "}}"));
+        if let Some(expr) = tpl.exprs.get(i) {
+            out.push('{');
+            out.push_str(&translate_expr(expr, ctx));
+            out.push('}');
+        }
+    }
+    out.push('"');
+    out
+}
+
+// =============================================================================
+// Hook Extraction Functions
+// =============================================================================
+
+fn extract_use_state(call: &CallExpr, binding: &Pat, component: &mut Component) {
+    if let Pat::Array(arr) = binding {
+        let var_name = arr.elems.get(0)
+            .and_then(|e| e.as_ref())
+            .and_then(|p| if let Pat::Ident(id) = p { Some(id.id.sym.to_string()) } else { None })
+            .unwrap_or_default();
+
+        let setter_name = arr.elems.get(1)
+            .and_then(|e| e.as_ref())
             .and_then(|p| if let Pat::Ident(id) = p { Some(id.id.sym.to_string()) } else { None })
             .unwrap_or_default();
 
+        let explicit_type = call
+            .type_args
+            .as_ref()
+            .and_then(|args| args.params.get(0))
+            .map(|ty| ts_type_to_csharp_type(ty));
+
+        let state_type = explicit_type.unwrap_or_else(|| {
+            call.args
+                .get(0)
+                .map(|arg| infer_csharp_type(&arg.expr))
+                .unwrap_or_else(|| "dynamic".to_string())
+        });
+
         let initial_value = call.args.get(0)
             .map(|arg| expr_to_csharp(&arg.expr))
-            .unwrap_or_else(|| "null".to_string());
+            .unwrap_or_else(|| get_default_value(&state_type));
 
         component.use_state.push(UseStateInfo {
             var_name,
             setter_name: Some(setter_name),
             initial_value,
-            state_type: "dynamic".to_string(),
+            state_type,
             is_client_state: false,
         });
     }
 }
 
 fn extract_use_effect(call: &CallExpr, component: &mut Component) {
-    let dependencies = if call.args.len() > 1 {
-        if let Some(arg) = call.args.get(1) {
-            extract_dependency_array(&arg.expr)
-        } else {
-            Vec::new()
+    let declared = call.args.get(1).map(|arg| extract_dependency_array(&arg.expr));
+    let inferred = call
+        .args
+        .get(0)
+        .map(|arg| infer_effect_dependencies(&arg.expr, component))
+        .unwrap_or_default();
+
+    let (dependencies, missing_dependencies, extra_dependencies) = match declared {
+        None => (inferred.clone(), Vec::new(), Vec::new()),
+        Some(declared) => {
+            let missing: Vec<String> = inferred.iter().filter(|d| !declared.contains(d)).cloned().collect();
+            let extra: Vec<String> = declared.iter().filter(|d| !inferred.contains(d)).cloned().collect();
+            (declared, missing, extra)
         }
-    } else {
-        Vec::new()
     };
 
     component.use_effect.push(UseEffectInfo {
         dependencies,
         is_client_side: false,
+        inferred_dependencies: inferred,
+        missing_dependencies,
+        extra_dependencies,
     });
 }
 
@@ -1644,15 +3789,28 @@ fn extract_use_client_state(call: &CallExpr, binding: &Pat, component: &mut Comp
             .and_then(|p| if let Pat::Ident(id) = p { Some(id.id.sym.to_string()) } else { None })
             .unwrap_or_default();
 
+        let explicit_type = call
+            .type_args
+            .as_ref()
+            .and_then(|args| args.params.get(0))
+            .map(|ty| ts_type_to_csharp_type(ty));
+
+        let state_type = explicit_type.unwrap_or_else(|| {
+            call.args
+                .get(0)
+                .map(|arg| infer_csharp_type(&arg.expr))
+                .unwrap_or_else(|| "dynamic".to_string())
+        });
+
         let initial_value = call.args.get(0)
             .map(|arg| expr_to_csharp(&arg.expr))
-            .unwrap_or_else(|| "null".to_string());
+            .unwrap_or_else(|| get_default_value(&state_type));
 
         component.use_client_state.push(UseStateInfo {
             var_name,
             setter_name: Some(setter_name),
             initial_value,
-            state_type: "dynamic".to_string(),
+            state_type,
             is_client_state: true,
         });
     }
@@ -1682,10 +3840,50 @@ fn extract_use_markdown(call: &CallExpr, binding: &Pat, component: &mut Componen
     }
 }
 
-fn extract_custom_hook(call: &CallExpr, binding: &Pat, hook_name: &str, component: &mut Component) {
+/// Local binding names the call site destructures a custom hook's result
+/// into - `{ data, refetch }` or `{ data: rows }` for `Pat::Object`,
+/// `[value, setValue]` for `Pat::Array` (mirroring the array handling in
+/// `extract_use_state`). Order follows the pattern; rest/spread elements
+/// are skipped since they don't name a single return value.
+fn custom_hook_return_bindings(binding: &Pat) -> Vec<String> {
+    match binding {
+        Pat::Object(obj) => obj.props.iter().filter_map(|prop| match prop {
+            ObjectPatProp::KeyValue(kv) => match &*kv.value {
+                Pat::Ident(id) => Some(id.id.sym.to_string()),
+                _ => None,
+            },
+            ObjectPatProp::Assign(assign) => Some(assign.key.sym.to_string()),
+            ObjectPatProp::Rest(_) => None,
+        }).collect(),
+        Pat::Array(arr) => arr.elems.iter()
+            .filter_map(|elem| match elem {
+                Some(Pat::Ident(id)) => Some(id.id.sym.to_string()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Extract a custom hook call site (`const x = useCounter(initial)`),
+/// shadowing the `helpers::extract_custom_hook_call` stub now that the
+/// same-file case is actually implemented. A hook not found in
+/// `definitions` is either imported from another file - in which case
+/// `build_hook_registry`/`link_custom_hooks` reconcile it once every file
+/// in the project has been transformed - or genuinely unresolved, and
+/// either way an instance reference with no hoisted state is the honest
+/// fallback.
+fn extract_custom_hook_call(
+    call: &CallExpr,
+    binding: &Pat,
+    hook_name: &str,
+    component: &mut Component,
+    definitions: &HashMap<String, CustomHookDefinition>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
     let instance_name = match binding {
         Pat::Ident(ident) => ident.id.sym.to_string(),
-        _ => return,
+        _ => String::new(),
     };
 
     let class_name = {
@@ -1693,14 +3891,229 @@ fn extract_custom_hook(call: &CallExpr, binding: &Pat, hook_name: &str, componen
         format!("{}Hook", without_use)
     };
 
+    let return_values = match definitions.get(hook_name) {
+        Some(definition) => {
+            hoist_custom_hook(component, definition, call);
+            match binding {
+                // The call site destructures the result itself
+                // (`{ data, refetch }` / `[value, setValue]`) - those local
+                // names are what the component body actually refers to, so
+                // they take precedence over the hook's own declared fields.
+                Pat::Object(_) | Pat::Array(_) => custom_hook_return_bindings(binding),
+                _ => match &definition.output {
+                    HookOutput::Fields(fields) => fields.clone(),
+                    HookOutput::Scalar => vec![instance_name.clone()],
+                    HookOutput::None => Vec::new(),
+                },
+            }
+        }
+        None => {
+            diagnostics.push(Diagnostic {
+                span: call.span,
+                severity: DiagnosticSeverity::Warning,
+                message: format!(
+                    "custom hook `{}` isn't defined in this file - assuming it's imported and will be linked once its file is transformed",
+                    hook_name
+                ),
+            });
+            custom_hook_return_bindings(binding)
+        }
+    };
+
     component.custom_hooks.push(CustomHookInstance {
         hook_name: hook_name.to_string(),
         instance_name,
         class_name,
-        return_values: Vec::new(),
+        return_values,
     });
 }
 
+/// Replay a custom hook's hoisted `useState`/`useEffect` calls into the
+/// calling component. Each hoisted state var is renamed with a counter
+/// suffix (`count_0`, `count_1`, ...) so two call sites - or two different
+/// hooks that both happen to declare a `count` - never collide, mirroring
+/// styled-components' `styled_idx` convention. An initial value that was
+/// just one of the hook's own inputs is substituted with this call's actual
+/// argument.
+fn hoist_custom_hook(component: &mut Component, definition: &CustomHookDefinition, call: &CallExpr) {
+    let suffix = component.use_state.len() + component.use_client_state.len();
+    let substitutions: HashMap<&str, String> = definition.inputs.iter()
+        .zip(call.args.iter())
+        .map(|(param, arg)| (param.as_str(), expr_to_csharp(&arg.expr)))
+        .collect();
+
+    for state in &definition.hoisted_state {
+        let mut hoisted = state.clone();
+        hoisted.var_name = format!("{}_{}", hoisted.var_name, suffix);
+        if let Some(replacement) = substitutions.get(hoisted.initial_value.as_str()) {
+            hoisted.initial_value = replacement.clone();
+        }
+        component.use_state.push(hoisted);
+    }
+
+    component.use_effect.extend(definition.hoisted_effects.iter().cloned());
+}
+
+/// Collects the free variables a custom hook's body references - used by
+/// `process_custom_hook` to derive the hook's actual inputs from usage
+/// rather than trusting its declared parameter list, the same
+/// declared-vs-referenced technique `CaptureCollector` uses for callback
+/// captures. Unlike `CaptureCollector`, the hook's own top-level parameters
+/// are deliberately left unbound going in, so a genuinely used one shows up
+/// as a referenced free variable - only things the body declares for
+/// itself (nested function/arrow/catch params, `let`/`const`) and module
+/// globals are excluded.
+struct HookFreeVariableCollector<'a> {
+    module_globals: &'a HashSet<String>,
+    scopes: Vec<HashSet<String>>,
+    referenced: Vec<String>,
+    seen: HashSet<String>,
+}
+
+impl<'a> HookFreeVariableCollector<'a> {
+    fn new(module_globals: &'a HashSet<String>) -> Self {
+        Self {
+            module_globals,
+            scopes: vec![HashSet::new()],
+            referenced: Vec::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    fn push_scope(&mut self) {
+        self.scopes.push(HashSet::new());
+    }
+
+    fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn bind(&mut self, name: String) {
+        self.scopes.last_mut().expect("at least one scope").insert(name);
+    }
+
+    fn bind_pat(&mut self, pat: &Pat) {
+        match pat {
+            Pat::Ident(ident) => self.bind(ident.id.sym.to_string()),
+            Pat::Array(arr) => {
+                for elem in arr.elems.iter().flatten() {
+                    self.bind_pat(elem);
+                }
+            }
+            Pat::Object(obj) => {
+                for prop in &obj.props {
+                    match prop {
+                        ObjectPatProp::KeyValue(kv) => self.bind_pat(&kv.value),
+                        ObjectPatProp::Assign(assign) => self.bind(assign.key.sym.to_string()),
+                        ObjectPatProp::Rest(rest) => self.bind_pat(&rest.arg),
+                    }
+                }
+            }
+            Pat::Rest(rest) => self.bind_pat(&rest.arg),
+            Pat::Assign(assign) => self.bind_pat(&assign.left),
+            Pat::Invalid(_) | Pat::Expr(_) => {}
+        }
+    }
+
+    fn is_locally_bound(&self, name: &str) -> bool {
+        self.scopes.iter().rev().any(|scope| scope.contains(name))
+    }
+
+    fn record(&mut self, name: String) {
+        if self.is_locally_bound(&name) || self.module_globals.contains(&name) || is_builtin_global(&name) {
+            return;
+        }
+        if self.seen.insert(name.clone()) {
+            self.referenced.push(name);
+        }
+    }
+}
+
+impl Visit for HookFreeVariableCollector<'_> {
+    fn visit_ident(&mut self, ident: &Ident) {
+        self.record(ident.sym.to_string());
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        // Don't record a hook call's own callee as a free variable - only
+        // its arguments (and any ordinary, non-hook call still falls
+        // through to default traversal, which visits the callee too).
+        if let Callee::Expr(callee) = &call.callee {
+            if let Expr::Ident(ident) = &**callee {
+                let name = ident.sym.to_string();
+                let is_hook_call = name.starts_with("use") && name.len() > 3
+                    && name.chars().nth(3).map(|c| c.is_uppercase()).unwrap_or(false);
+                if is_hook_call {
+                    for arg in &call.args {
+                        arg.visit_with(self);
+                    }
+                    return;
+                }
+            }
+        }
+        call.visit_children_with(self);
+    }
+
+    fn visit_function(&mut self, func: &Function) {
+        self.push_scope();
+        for param in &func.params {
+            self.bind_pat(&param.pat);
+        }
+        func.visit_children_with(self);
+        self.pop_scope();
+    }
+
+    fn visit_arrow_expr(&mut self, arrow: &ArrowExpr) {
+        self.push_scope();
+        for pat in &arrow.params {
+            self.bind_pat(pat);
+        }
+        arrow.visit_children_with(self);
+        self.pop_scope();
+    }
+
+    fn visit_block_stmt(&mut self, block: &BlockStmt) {
+        self.push_scope();
+        block.visit_children_with(self);
+        self.pop_scope();
+    }
+
+    fn visit_var_declarator(&mut self, decl: &VarDeclarator) {
+        if let Some(init) = &decl.init {
+            init.visit_with(self);
+        }
+        self.bind_pat(&decl.name);
+    }
+
+    fn visit_catch_clause(&mut self, catch: &CatchClause) {
+        self.push_scope();
+        if let Some(param) = &catch.param {
+            self.bind_pat(param);
+        }
+        catch.body.visit_children_with(self);
+        self.pop_scope();
+    }
+
+    fn visit_fn_decl(&mut self, fn_decl: &FnDecl) {
+        self.bind(fn_decl.ident.sym.to_string());
+        self.push_scope();
+        for param in &fn_decl.function.params {
+            self.bind_pat(&param.pat);
+        }
+        if let Some(body) = &fn_decl.function.body {
+            body.visit_children_with(self);
+        }
+        self.pop_scope();
+    }
+}
+
+fn is_builtin_global(name: &str) -> bool {
+    matches!(name,
+        "console" | "Math" | "JSON" | "Object" | "Array" | "Number" | "String" | "Boolean"
+            | "Date" | "Promise" | "window" | "document" | "undefined" | "NaN" | "Infinity"
+    )
+}
+
 fn extract_dependency_array(expr: &Expr) -> Vec<String> {
     match expr {
         Expr::Array(arr) => {
@@ -1731,7 +4144,13 @@ fn expr_to_csharp(expr: &Expr) -> String {
         Expr::Ident(ident) => ident.sym.to_string(),
         Expr::Array(_) => "new List<dynamic>()".to_string(),
         Expr::Object(_) => "new Dictionary<string, dynamic>()".to_string(),
-        _ => "null".to_string(),
+        // Anything else - `1 + 2`, `!flag`, `cond ? 1 : 2` - gets a real
+        // literal when it's statically computable instead of defaulting
+        // straight to "null".
+        _ => match fold_expr(expr) {
+            Value::Known(value) => value.to_csharp_literal(),
+            Value::Unknown => "null".to_string(),
+        },
     }
 }
 
@@ -1741,3 +4160,357 @@ pub fn process_transform(mut program: Program, input_file_path: String) -> Progr
     program.visit_mut_with(&mut transformer);
     program
 }
+
+/// Like `process_transform`, but also hands back this file's components
+/// and what it exports, for a build driver doing project-level module
+/// resolution (see "Project-level module resolution" below).
+pub fn process_transform_with_exports(mut program: Program, input_file_path: String) -> (Program, Vec<Component>, FileExports) {
+    let mut transformer = MinimactTransformer::new(input_file_path);
+    program.visit_mut_with(&mut transformer);
+    (program, transformer.components, transformer.file_exports)
+}
+
+/// One component's generated output, as `render_component_output` renders
+/// it - the in-memory equivalent of the files `generate_component_outputs`
+/// writes to disk. `templates_json`/`timeline_json` are `None` under the
+/// same conditions those files are skipped on the writing path.
+#[derive(Clone, Debug)]
+pub struct ComponentOutput {
+    pub name: String,
+    pub csharp: String,
+    pub templates_json: Option<String>,
+    pub timeline_json: Option<String>,
+    pub structural_changes_json: String,
+    pub handlebars: String,
+}
+
+/// Result of `transform_source_in_memory`: every component's rendered
+/// output plus any diagnostics recorded while transforming - a parse
+/// failure is reported as a single error-severity diagnostic with no
+/// components, rather than a `Result::Err`, so callers always get the
+/// same shape back regardless of what went wrong.
+pub struct InMemoryTransformResult {
+    pub components: Vec<ComponentOutput>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Parse and transform a standalone TSX source string entirely in memory,
+/// returning the generated C#, templates JSON, and Handlebars output per
+/// component instead of writing them to disk. This is what an interactive
+/// playground/REPL needs - paste a component, see the emitted C# and
+/// extracted templates immediately - and lets editors or test harnesses
+/// drive the transpiler programmatically without a real file on disk.
+///
+/// `virtual_path` is only used to name the source and label diagnostics;
+/// it's never read from or written to.
+pub fn transform_source_in_memory(source: &str, virtual_path: String) -> InMemoryTransformResult {
+    let source_map = SourceMap::default();
+    let source_file = source_map.new_source_file(FileName::Custom(virtual_path.clone()), source.to_string());
+
+    let syntax = Syntax::Typescript(TsConfig { tsx: true, ..Default::default() });
+    let lexer = Lexer::new(syntax, Default::default(), StringInput::from(&*source_file), None);
+    let mut parser = Parser::new_from(lexer);
+
+    let module = match parser.parse_module() {
+        Ok(module) => module,
+        Err(error) => {
+            return InMemoryTransformResult {
+                components: Vec::new(),
+                diagnostics: vec![Diagnostic {
+                    span: error.span(),
+                    severity: DiagnosticSeverity::Error,
+                    message: format!("failed to parse {} as TSX", virtual_path),
+                }],
+            };
+        }
+    };
+
+    // An empty path makes `generate_outputs` return before it reads or
+    // writes anything.
+    let mut transformer = MinimactTransformer::new(String::new());
+    let mut program = Program::Module(module);
+    program.visit_mut_with(&mut transformer);
+
+    // No previous build to diff against off the filesystem - every render
+    // is treated as this component's first mount, same as
+    // `generate_structural_changes_json` does for a brand new file on the
+    // writing path.
+    let no_previous_build = std::path::Path::new("");
+    let components = transformer
+        .components
+        .iter()
+        .map(|component| transformer.render_component_output(component, no_previous_build))
+        .collect();
+
+    InMemoryTransformResult { components, diagnostics: transformer.diagnostics }
+}
+
+// =============================================================================
+// Project-level hook linking
+//
+// `process_transform` only sees one file at a time, so a custom hook's
+// `Component.custom_hooks` instance and the sibling file that actually
+// defines that hook class have to be reconciled by whatever drives the
+// build across a whole directory. These two passes are that reconciliation:
+// call `build_hook_registry` once all files have been transformed, then
+// `link_custom_hooks` per component. Each file's C#/templates output was
+// already written by `generate_outputs` during its transform, so the
+// metadata linked here does not reach that output.
+// =============================================================================
+
+/// Registry of custom hook classes discovered across an entire project
+/// build, keyed by class name.
+pub type HookRegistry = HashMap<String, HookMetadata>;
+
+/// First pass: fold every component's own `imported_hook_metadata` into one
+/// project-wide registry, keyed by hook class name. A hook class is only
+/// present here once something in the project has actually extracted its
+/// metadata (see `analyze_hook`); classes nobody has analyzed yet simply
+/// won't resolve in the second pass.
+pub fn build_hook_registry<'a>(components: impl IntoIterator<Item = &'a Component>) -> HookRegistry {
+    let mut registry = HashMap::new();
+    for component in components {
+        for (class_name, metadata) in &component.imported_hook_metadata {
+            registry.entry(class_name.clone()).or_insert_with(|| metadata.clone());
+        }
+    }
+    registry
+}
+
+/// Second pass: for every `custom_hooks` instance `component` references
+/// that it doesn't already have metadata for, pull it from the project-wide
+/// `registry` instead. Returns the class names that no file in the project
+/// defines, so the build driver can report a clear "missing hook class"
+/// error rather than silently generating an incomplete class.
+pub fn link_custom_hooks(component: &mut Component, registry: &HookRegistry) -> Vec<String> {
+    let mut missing = Vec::new();
+    for hook_instance in &component.custom_hooks {
+        if component.imported_hook_metadata.contains_key(&hook_instance.class_name) {
+            continue;
+        }
+        match registry.get(&hook_instance.class_name) {
+            Some(metadata) => {
+                component
+                    .imported_hook_metadata
+                    .insert(hook_instance.class_name.clone(), metadata.clone());
+            }
+            None => missing.push(hook_instance.class_name.clone()),
+        }
+    }
+    missing
+}
+
+// =============================================================================
+// Project-level module resolution
+//
+// `process_transform` only sees one file at a time, so resolving a relative
+// import (`./Button`) to the component it actually defines, ordering output
+// generation so a component's dependencies are analyzed first, and
+// detecting import cycles all have to happen after every file in the
+// project has been transformed - the same shape as the hook-linking pass
+// above. Call `build_module_graph` once every file has been run through
+// `process_transform_with_exports`, then `link_component_references` per
+// component. Each file's output was already written by `generate_outputs`
+// during its transform, so the references resolved here do not reach it.
+// =============================================================================
+
+/// One transformed file, as the build driver would have it after calling
+/// `process_transform_with_exports`.
+pub struct FileModule {
+    /// Relative to the project root - the same path space `source` in a
+    /// relative import is resolved against.
+    pub path: String,
+    pub components: Vec<Component>,
+    pub exports: FileExports,
+}
+
+/// Cross-file dependency graph, keyed by file path.
+pub struct ModuleGraph {
+    /// File path -> the file paths it relatively imports from.
+    pub edges: HashMap<String, Vec<String>>,
+    /// Import cycles found, each as the file path sequence that closes the
+    /// loop (the first and last entries are the same file).
+    pub cycles: Vec<Vec<String>>,
+}
+
+/// Resolve a relative import specifier (`./Button`, `../shared/Card`)
+/// against the file that imports it, the way an ES module linker would -
+/// purely lexical, no filesystem access (the sibling `.tsx` files aren't
+/// necessarily readable from wherever this runs as an SWC plugin).
+fn resolve_relative_path(importer_path: &str, specifier: &str) -> String {
+    let importer_dir = std::path::Path::new(importer_path).parent().unwrap_or_else(|| std::path::Path::new(""));
+    let mut segments: Vec<String> =
+        importer_dir.components().map(|c| c.as_os_str().to_string_lossy().to_string()).collect();
+
+    for part in specifier.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment.to_string()),
+        }
+    }
+
+    segments.join("/")
+}
+
+/// Build the project's component dependency graph: for every file, which
+/// other files it relatively imports from (via any JSX tag resolved to a
+/// `ReferenceSource::Relative` in `collect_component_references`).
+pub fn build_module_graph(files: &[FileModule]) -> ModuleGraph {
+    let mut edges: HashMap<String, Vec<String>> = HashMap::new();
+
+    for file in files {
+        let mut deps = Vec::new();
+        for component in &file.components {
+            for reference in &component.component_references {
+                if let ReferenceSource::Relative(specifier) = &reference.source {
+                    let target = resolve_relative_path(&file.path, specifier);
+                    if !deps.contains(&target) {
+                        deps.push(target);
+                    }
+                }
+            }
+        }
+        edges.insert(file.path.clone(), deps);
+    }
+
+    let cycles = find_import_cycles(&edges);
+    ModuleGraph { edges, cycles }
+}
+
+/// DFS cycle detection over the file-dependency graph - standard
+/// visited/on-stack bookkeeping, since `edges` is small enough (one entry
+/// per source file) that recursion depth isn't a concern.
+fn find_import_cycles(edges: &HashMap<String, Vec<String>>) -> Vec<Vec<String>> {
+    let mut cycles = Vec::new();
+    let mut visited = HashSet::new();
+
+    let mut files: Vec<&String> = edges.keys().collect();
+    files.sort();
+
+    for start in files {
+        if !visited.contains(start) {
+            let mut stack = Vec::new();
+            find_import_cycles_from(start, edges, &mut stack, &mut visited, &mut cycles);
+        }
+    }
+
+    cycles
+}
+
+fn find_import_cycles_from(
+    node: &str,
+    edges: &HashMap<String, Vec<String>>,
+    stack: &mut Vec<String>,
+    visited: &mut HashSet<String>,
+    cycles: &mut Vec<Vec<String>>,
+) {
+    stack.push(node.to_string());
+
+    if let Some(deps) = edges.get(node) {
+        for dep in deps {
+            if let Some(start) = stack.iter().position(|n| n == dep) {
+                let mut cycle = stack[start..].to_vec();
+                cycle.push(dep.clone());
+                cycles.push(cycle);
+            } else if !visited.contains(dep) {
+                find_import_cycles_from(dep, edges, stack, visited, cycles);
+            }
+        }
+    }
+
+    stack.pop();
+    visited.insert(node.to_string());
+}
+
+/// Files in dependency-first order - a file's relative imports are ordered
+/// before it is, so a build driver can analyze/codegen each file only
+/// after everything it depends on. Files caught in a cycle (see
+/// `ModuleGraph::cycles`) have no valid topological position, so they're
+/// appended at the end in file-path order instead of being silently
+/// dropped from the result.
+pub fn topological_order(graph: &ModuleGraph) -> Vec<String> {
+    let mut remaining_deps: HashMap<&str, usize> = graph
+        .edges
+        .iter()
+        .map(|(file, deps)| (file.as_str(), deps.iter().filter(|d| graph.edges.contains_key(d.as_str())).count()))
+        .collect();
+
+    let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (file, deps) in &graph.edges {
+        for dep in deps {
+            if graph.edges.contains_key(dep) {
+                dependents.entry(dep.as_str()).or_default().push(file.as_str());
+            }
+        }
+    }
+
+    let mut ready: std::collections::VecDeque<&str> =
+        remaining_deps.iter().filter(|(_, count)| **count == 0).map(|(file, _)| *file).collect();
+    let mut ready_sorted: Vec<&str> = ready.drain(..).collect();
+    ready_sorted.sort();
+    ready.extend(ready_sorted);
+
+    let mut order = Vec::new();
+    while let Some(file) = ready.pop_front() {
+        order.push(file.to_string());
+        if let Some(deps) = dependents.get(file) {
+            let mut newly_ready = Vec::new();
+            for dependent in deps {
+                if let Some(count) = remaining_deps.get_mut(dependent) {
+                    *count -= 1;
+                    if *count == 0 {
+                        newly_ready.push(*dependent);
+                    }
+                }
+            }
+            newly_ready.sort();
+            ready.extend(newly_ready);
+        }
+    }
+
+    let mut leftover: Vec<&str> = graph.edges.keys().map(|s| s.as_str()).filter(|f| !order.contains(f)).collect();
+    leftover.sort();
+    order.extend(leftover.iter().map(|s| s.to_string()));
+
+    order
+}
+
+/// For every `ComponentReference` this component has, resolve it against
+/// `exports_by_file` - looking the reference's relative specifier up as a
+/// file path and checking whether that file's exports name `tag_name`.
+/// Returns the tag names that couldn't be resolved (not exported by the
+/// target file, or the target file wasn't in `exports_by_file` at all), so
+/// the build driver can report a clear error instead of silently emitting
+/// a call to a component that doesn't exist.
+pub fn link_component_references(
+    component: &mut Component,
+    importer_path: &str,
+    exports_by_file: &HashMap<String, FileExports>,
+) -> Vec<String> {
+    let mut missing = Vec::new();
+
+    for reference in &mut component.component_references {
+        let ReferenceSource::Relative(specifier) = &reference.source else {
+            continue;
+        };
+
+        let target_path = resolve_relative_path(importer_path, specifier);
+        let resolved = exports_by_file.get(&target_path).and_then(|exports| {
+            if exports.named.contains(&reference.tag_name) {
+                Some(reference.tag_name.clone())
+            } else {
+                exports.default.clone()
+            }
+        });
+
+        match resolved {
+            Some(class_name) => reference.resolved_class_name = Some(class_name),
+            None => missing.push(reference.tag_name.clone()),
+        }
+    }
+
+    missing
+}
@@ -0,0 +1,157 @@
+//! `rustscript.toml` config loading.
+//!
+//! Lets users set defaults (target, output directory, log level) once
+//! instead of passing `--target`/`--output` on every invocation. Resolution
+//! order is always CLI flag > config value > built-in default; callers in
+//! `main.rs` are responsible for applying that precedence themselves since
+//! each subcommand's built-in default differs.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CONFIG_FILE_NAME: &str = "rustscript.toml";
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum LogLevel {
+    Quiet,
+    Normal,
+    Verbose,
+}
+
+impl std::str::FromStr for LogLevel {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "quiet" => Ok(LogLevel::Quiet),
+            "normal" => Ok(LogLevel::Normal),
+            "verbose" => Ok(LogLevel::Verbose),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Resolved `rustscript.toml` settings. Fields are `None`/empty when the
+/// config didn't set them, so CLI code can fall back to its own default.
+pub struct Settings {
+    pub target: Option<String>,
+    pub output: Option<PathBuf>,
+    pub log_level: LogLevel,
+    /// Pinned `PluginUsage.version` per plugin name. Not interpreted by
+    /// rustscript itself (it has no plugin concept) - this exists so the
+    /// same config file can also drive a generated-swc build of the
+    /// project's components, which does.
+    pub plugin_versions: HashMap<String, String>,
+    /// Default `runtime` for `UseServerTaskInfo`/`PaginatedTaskInfo`, same
+    /// pass-through caveat as `plugin_versions`.
+    pub default_server_task_runtime: Option<String>,
+    pub config_path: Option<PathBuf>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            target: None,
+            output: None,
+            log_level: LogLevel::Normal,
+            plugin_versions: HashMap::new(),
+            default_server_task_runtime: None,
+            config_path: None,
+        }
+    }
+}
+
+/// Load settings for a build/check rooted at `start` (the input file or
+/// directory). `explicit_config`, from `--config`, skips the upward search
+/// entirely. Returns built-in defaults (not an error) if no config exists
+/// or it fails to parse - a missing `rustscript.toml` is the common case.
+pub fn load_settings(explicit_config: Option<&Path>, start: &Path) -> Settings {
+    let config_path = explicit_config.map(Path::to_path_buf).or_else(|| find_config(start));
+
+    let mut settings = match &config_path {
+        Some(path) => match fs::read_to_string(path) {
+            Ok(source) => Settings::from_raw(parse_toml(&source)),
+            Err(e) => {
+                eprintln!("Warning: could not read config {:?}: {}", path, e);
+                Settings::default()
+            }
+        },
+        None => Settings::default(),
+    };
+
+    settings.config_path = config_path;
+    settings
+}
+
+fn find_config(start: &Path) -> Option<PathBuf> {
+    let mut dir = if start.is_dir() {
+        Some(start.to_path_buf())
+    } else {
+        start.parent().map(Path::to_path_buf)
+    };
+
+    while let Some(d) = dir {
+        let candidate = d.join(CONFIG_FILE_NAME);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        dir = d.parent().map(Path::to_path_buf);
+    }
+    None
+}
+
+impl Settings {
+    fn from_raw(raw: HashMap<String, String>) -> Settings {
+        let mut plugin_versions = HashMap::new();
+        let mut default_server_task_runtime = None;
+
+        for (key, value) in &raw {
+            if let Some(plugin_name) = key.strip_prefix("plugins.").and_then(|rest| rest.strip_suffix(".version")) {
+                plugin_versions.insert(plugin_name.to_string(), value.clone());
+            }
+            if key == "server_tasks.default_runtime" {
+                default_server_task_runtime = Some(value.clone());
+            }
+        }
+
+        Settings {
+            target: raw.get("target").cloned(),
+            output: raw.get("output").map(PathBuf::from),
+            log_level: raw.get("log_level").and_then(|v| v.parse().ok()).unwrap_or(LogLevel::Normal),
+            plugin_versions,
+            default_server_task_runtime,
+            config_path: None,
+        }
+    }
+}
+
+/// A minimal TOML subset: `key = "value"` / `key = value` pairs and
+/// `[section]`/`[section.sub]` headers, flattened into a single map keyed
+/// by dotted path (`section.key`). No arrays, inline tables, or multi-line
+/// strings - rustscript.toml only needs flat scalar settings.
+fn parse_toml(source: &str) -> HashMap<String, String> {
+    let mut section = String::new();
+    let mut values = HashMap::new();
+
+    for raw_line in source.lines() {
+        let line = raw_line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with('[') && line.ends_with(']') {
+            section = line[1..line.len() - 1].trim().to_string();
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once('=') {
+            let key = key.trim();
+            let value = value.trim().trim_matches('"').to_string();
+            let full_key = if section.is_empty() { key.to_string() } else { format!("{}.{}", section, key) };
+            values.insert(full_key, value);
+        }
+    }
+
+    values
+}
@@ -0,0 +1,137 @@
+//! Rich diagnostic rendering for the CLI.
+//!
+//! Turns a bare `error[code]: message at line:col` into a rustc-style
+//! report with the offending source line, a caret pointing at the
+//! reported column, and the hint as a secondary annotation. Color is only
+//! emitted when stderr is a TTY.
+
+use std::io::IsTerminal;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    fn label(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+
+    fn color_code(self) -> &'static str {
+        match self {
+            Severity::Error => "31",   // red
+            Severity::Warning => "33", // yellow
+        }
+    }
+}
+
+/// A single error or warning ready to render. Callers flatten their
+/// concrete error type (`ParseError`, `SemanticError`, ...) into this shape
+/// so the renderer doesn't need to know about any of them.
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: String,
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+    pub hint: Option<String>,
+}
+
+/// Render every diagnostic for `file` as a single JSON array on stdout, for
+/// editor/LSP tooling that wants a stable contract instead of scraping the
+/// human-formatted report.
+pub fn render_diagnostics_json(file: &str, diagnostics: &mut [Diagnostic]) {
+    diagnostics.sort_by_key(|d| (d.line, d.column));
+
+    let items: Vec<String> = diagnostics
+        .iter()
+        .map(|d| {
+            format!(
+                "{{\"severity\":{},\"code\":{},\"message\":{},\"hint\":{},\"file\":{},\"span\":{{\"line\":{},\"column\":{}}}}}",
+                json_string(d.severity.label()),
+                json_string(&d.code),
+                json_string(&d.message),
+                d.hint.as_deref().map(json_string).unwrap_or_else(|| "null".to_string()),
+                json_string(file),
+                d.line,
+                d.column,
+            )
+        })
+        .collect();
+
+    println!("[{}]", items.join(","));
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Render every diagnostic against `source`, aggregated and sorted by
+/// source position (line, then column) rather than discovery order.
+pub fn render_diagnostics(source: &str, diagnostics: &mut [Diagnostic]) {
+    diagnostics.sort_by_key(|d| (d.line, d.column));
+
+    let lines: Vec<&str> = source.lines().collect();
+    let color = std::io::stderr().is_terminal();
+
+    for diagnostic in diagnostics.iter() {
+        render_one(&lines, diagnostic, color);
+    }
+}
+
+fn render_one(lines: &[&str], diagnostic: &Diagnostic, color: bool) {
+    let header = format!(
+        "{}[{}]: {}",
+        diagnostic.severity.label(),
+        diagnostic.code,
+        diagnostic.message
+    );
+    if color {
+        eprintln!("\x1B[1;{}m{}\x1B[0m", diagnostic.severity.color_code(), header);
+    } else {
+        eprintln!("{}", header);
+    }
+
+    eprintln!("  --> {}:{}", diagnostic.line, diagnostic.column);
+
+    if let Some(source_line) = lines.get(diagnostic.line.saturating_sub(1)) {
+        let gutter = format!("{} | ", diagnostic.line);
+        eprintln!("{}{}", gutter, source_line);
+
+        let caret_column = diagnostic.column.saturating_sub(1);
+        let padding = " ".repeat(gutter.len() + caret_column);
+        if color {
+            eprintln!("{}\x1B[1;{}m^\x1B[0m", padding, diagnostic.severity.color_code());
+        } else {
+            eprintln!("{}^", padding);
+        }
+    }
+
+    if let Some(hint) = &diagnostic.hint {
+        if color {
+            eprintln!("  \x1B[1;36mhelp\x1B[0m: {}", hint);
+        } else {
+            eprintln!("  help: {}", hint);
+        }
+    }
+
+    eprintln!();
+}
@@ -9,11 +9,25 @@ use rustscript::{Lexer, Parser, analyze};
 #[cfg(feature = "codegen")]
 use rustscript::{generate, Target, lower};
 
+mod diagnostics;
+use diagnostics::{render_diagnostics, render_diagnostics_json, Diagnostic, Severity};
+
+#[cfg(feature = "codegen")]
+mod testing;
+
+mod config;
+use config::{load_settings, LogLevel, Settings};
+
 #[derive(ClapParser)]
 #[command(name = "rustscript")]
 #[command(about = "RustScript compiler - compile to Babel and SWC plugins")]
 #[command(version)]
 struct Cli {
+    /// Path to a rustscript.toml config file (default: nearest one found by
+    /// walking up from the input path)
+    #[arg(short, long, global = true)]
+    config: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -34,23 +48,83 @@ enum Commands {
     Check {
         /// Input file
         file: PathBuf,
+        /// Re-check whenever the file changes on disk
+        #[arg(short, long)]
+        watch: bool,
+        /// Diagnostic output format (human, json)
+        #[arg(short, long, default_value = "human")]
+        format: String,
     },
     /// Build a RustScript project
     #[cfg(feature = "codegen")]
     Build {
         /// Input file
         file: PathBuf,
-        /// Target platform (babel, swc, both)
-        #[arg(short, long, default_value = "both")]
-        target: String,
-        /// Output directory
-        #[arg(short, long, default_value = "dist")]
-        output: PathBuf,
+        /// Target platform (babel, swc, both). Defaults to the `target` set
+        /// in rustscript.toml, or "both" if there is none.
+        #[arg(short, long)]
+        target: Option<String>,
+        /// Output directory. Defaults to the `output` set in
+        /// rustscript.toml, or "dist" if there is none.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Rebuild whenever the file changes on disk (files it imports are
+        /// not watched)
+        #[arg(short, long)]
+        watch: bool,
+        /// Diagnostic output format (human, json)
+        #[arg(short, long, default_value = "human")]
+        format: String,
+    },
+    /// Start an interactive REPL for incremental compilation
+    Repl,
+    /// Snapshot-test generated output for every RustScript file under a directory
+    #[cfg(feature = "codegen")]
+    Test {
+        /// Directory to search for RustScript files
+        #[arg(default_value = ".")]
+        dir: PathBuf,
+        /// Glob pattern (relative to `dir`) selecting files to test
+        #[arg(short, long, default_value = "**/*.rs")]
+        include: String,
+        /// Glob pattern (relative to `dir`) excluding files that would otherwise match
+        #[arg(short, long)]
+        exclude: Option<String>,
+        /// Rewrite snapshots to match current output instead of failing on mismatch
+        #[arg(short, long)]
+        update: bool,
     },
 }
 
+/// Print what was resolved from `rustscript.toml`, only at `verbose` level -
+/// the common case (no config, or everything overridden on the CLI) stays
+/// silent.
+fn log_settings(settings: &Settings) {
+    if settings.log_level != LogLevel::Verbose {
+        return;
+    }
+
+    match &settings.config_path {
+        Some(path) => println!("Loaded config: {:?}", path),
+        None => println!("No rustscript.toml found; using built-in defaults"),
+    }
+    if let Some(target) = &settings.target {
+        println!("  target = {}", target);
+    }
+    if let Some(output) = &settings.output {
+        println!("  output = {:?}", output);
+    }
+    if !settings.plugin_versions.is_empty() {
+        println!("  plugin versions = {:?}", settings.plugin_versions);
+    }
+    if let Some(runtime) = &settings.default_server_task_runtime {
+        println!("  server_tasks.default_runtime = {}", runtime);
+    }
+}
+
 fn main() {
     let cli = <Cli as ClapParser>::parse();
+    let explicit_config = cli.config.clone();
 
     match cli.command {
         Commands::Lex { file } => {
@@ -103,140 +177,618 @@ fn main() {
                 }
             }
         }
-        Commands::Check { file } => {
-            let source = match fs::read_to_string(&file) {
-                Ok(s) => s,
-                Err(e) => {
-                    eprintln!("Error reading file: {}", e);
-                    std::process::exit(1);
-                }
-            };
+        Commands::Check { file, watch, format } => {
+            let settings = load_settings(explicit_config.as_deref(), &file);
+            log_settings(&settings);
+            let quiet = settings.log_level == LogLevel::Quiet;
 
-            let mut lexer = Lexer::new(&source);
-            let tokens = lexer.tokenize();
-            let mut parser = Parser::new(tokens);
-
-            let program = match parser.parse() {
-                Ok(p) => p,
-                Err(e) => {
-                    eprintln!("Parse error at {}:{}: {}", e.span.line, e.span.column, e.message);
-                    std::process::exit(1);
-                }
-            };
+            let check = |file: &PathBuf| run_check(file, &format, quiet);
+            if watch {
+                watch_loop(&file, check);
+            } else if !check(&file).success {
+                std::process::exit(1);
+            }
+        }
+        #[cfg(feature = "codegen")]
+        Commands::Build { file, target, output, watch, format } => {
+            let settings = load_settings(explicit_config.as_deref(), &file);
+            log_settings(&settings);
+            let quiet = settings.log_level == LogLevel::Quiet;
 
-            let result = analyze(&program);
+            let target = target.unwrap_or_else(|| settings.target.clone().unwrap_or_else(|| "both".to_string()));
+            let output = output.unwrap_or_else(|| settings.output.clone().unwrap_or_else(|| PathBuf::from("dist")));
 
-            // Print errors
-            for error in &result.errors {
-                eprintln!(
-                    "error[{}]: {} at {}:{}",
-                    error.code, error.message, error.span.line, error.span.column
-                );
-                if let Some(ref hint) = error.hint {
-                    eprintln!("  help: {}", hint);
-                }
+            let build = |file: &PathBuf| run_build(file, &target, &output, &format, quiet);
+            if watch {
+                watch_loop(&file, build);
+            } else if !build(&file).success {
+                std::process::exit(1);
             }
+        }
+        Commands::Repl => {
+            let cwd = std::env::current_dir().unwrap_or_default();
+            let settings = load_settings(explicit_config.as_deref(), &cwd);
+            log_settings(&settings);
+            run_repl(settings.log_level == LogLevel::Quiet);
+        }
+        #[cfg(feature = "codegen")]
+        Commands::Test { dir, include, exclude, update } => {
+            let settings = load_settings(explicit_config.as_deref(), &dir);
+            log_settings(&settings);
+            let quiet = settings.log_level == LogLevel::Quiet;
 
-            // Print warnings
-            for warning in &result.warnings {
-                eprintln!(
-                    "warning[{}]: {} at {}:{}",
-                    warning.code, warning.message, warning.span.line, warning.span.column
-                );
-                if let Some(ref hint) = warning.hint {
-                    eprintln!("  help: {}", hint);
-                }
+            let files = testing::collect_rustscript_files(&dir, &include, exclude.as_deref());
+            if files.is_empty() {
+                println!("No files under {:?} matched {:?}", dir, include);
+                return;
             }
 
-            if result.errors.is_empty() {
-                println!("Check passed: {:?}", file);
-                if !result.warnings.is_empty() {
-                    println!("  {} warning(s)", result.warnings.len());
-                }
-            } else {
-                eprintln!("Check failed: {} error(s)", result.errors.len());
+            let results = testing::run_tests_parallel(&files, update);
+            let failures: usize = results
+                .iter()
+                .map(|result| print_test_case_result(&dir, result, quiet))
+                .sum();
+
+            println!();
+            println!("{} file(s) checked, {} failure(s)", results.len(), failures);
+            if failures > 0 {
                 std::process::exit(1);
             }
         }
-        #[cfg(feature = "codegen")]
-        Commands::Build { file, target, output } => {
-            let source = match fs::read_to_string(&file) {
-                Ok(s) => s,
-                Err(e) => {
-                    eprintln!("Error reading file: {}", e);
-                    std::process::exit(1);
-                }
-            };
+    }
+}
 
-            // Parse
-            let mut lexer = Lexer::new(&source);
-            let tokens = lexer.tokenize();
-            let mut parser = Parser::new(tokens);
+#[cfg(feature = "codegen")]
+fn print_test_case_result(base: &std::path::Path, result: &testing::TestCaseResult, quiet: bool) -> usize {
+    let relative = result.path.strip_prefix(base).unwrap_or(&result.path);
 
-            let mut program = match parser.parse() {
-                Ok(p) => p,
-                Err(e) => {
-                    eprintln!("Parse error at {}:{}: {}", e.span.line, e.span.column, e.message);
-                    std::process::exit(1);
-                }
-            };
+    if let testing::SnapshotCheck::ParseFailed(message) = &result.babel {
+        println!("FAILED   {} ({})", relative.display(), message);
+        return 1;
+    }
 
-            // Semantic analysis
-            let result = analyze(&program);
-            if !result.errors.is_empty() {
-                for error in &result.errors {
-                    eprintln!(
-                        "error[{}]: {} at {}:{}",
-                        error.code, error.message, error.span.line, error.span.column
-                    );
-                }
-                eprintln!("Build failed: {} error(s)", result.errors.len());
-                std::process::exit(1);
+    let mut failed = false;
+    for (kind, check) in [("babel", &result.babel), ("swc", &result.swc)] {
+        match check {
+            testing::SnapshotCheck::Match | testing::SnapshotCheck::NotApplicable => {}
+            testing::SnapshotCheck::Created => println!("created  {} [{}]", relative.display(), kind),
+            testing::SnapshotCheck::Updated => println!("updated  {} [{}]", relative.display(), kind),
+            testing::SnapshotCheck::Mismatch(diff) => {
+                failed = true;
+                println!("FAILED   {} [{}]", relative.display(), kind);
+                print!("{}", diff);
             }
+            testing::SnapshotCheck::ParseFailed(_) => unreachable!("handled above"),
+        }
+    }
+    if !failed && !quiet {
+        println!("ok       {}", relative.display());
+    }
+    failed as usize
+}
 
-            // AST lowering (transform deep chains to pattern matching)
-            lower(&mut program);
+// =============================================================================
+// REPL
+// =============================================================================
 
-            // Determine target
-            let target_enum = match target.as_str() {
-                "babel" => Target::Babel,
-                "swc" => Target::Swc,
-                "both" => Target::Both,
-                _ => {
-                    eprintln!("Unknown target: {}. Use 'babel', 'swc', or 'both'", target);
-                    std::process::exit(1);
-                }
-            };
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum ReplMode {
+    Tokens,
+    Ast,
+    Check,
+    Babel,
+    Swc,
+}
 
-            // Generate code
-            let generated = generate(&program, target_enum);
+impl ReplMode {
+    fn name(&self) -> &'static str {
+        match self {
+            ReplMode::Tokens => "tokens",
+            ReplMode::Ast => "ast",
+            ReplMode::Check => "check",
+            ReplMode::Babel => "babel",
+            ReplMode::Swc => "swc",
+        }
+    }
+}
 
-            // Create output directory
-            if let Err(e) = fs::create_dir_all(&output) {
-                eprintln!("Error creating output directory: {}", e);
-                std::process::exit(1);
-            }
+impl std::str::FromStr for ReplMode {
+    type Err = String;
 
-            // Write generated files
-            if let Some(babel_code) = generated.babel {
-                let babel_path = output.join("index.js");
-                if let Err(e) = fs::write(&babel_path, babel_code) {
-                    eprintln!("Error writing Babel output: {}", e);
-                    std::process::exit(1);
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tokens" => Ok(ReplMode::Tokens),
+            "ast" => Ok(ReplMode::Ast),
+            "check" => Ok(ReplMode::Check),
+            "babel" => Ok(ReplMode::Babel),
+            "swc" => Ok(ReplMode::Swc),
+            other => Err(format!(
+                "Unknown mode: {}. Use tokens, ast, check, babel, or swc",
+                other
+            )),
+        }
+    }
+}
+
+const REPL_HISTORY_FILE: &str = ".rustscript_history";
+
+fn run_repl(quiet: bool) {
+    use std::io::Write;
+
+    if !quiet {
+        println!("RustScript REPL - `:help` for commands, `:quit` to exit");
+    }
+
+    let history_path = PathBuf::from(REPL_HISTORY_FILE);
+    let mut mode = ReplMode::Ast;
+    let mut buffer = String::new();
+    let stdin = std::io::stdin();
+
+    loop {
+        print!("{}", if buffer.is_empty() { "rustscript> " } else { "       ... " });
+        let _ = std::io::stdout().flush();
+
+        let mut line = String::new();
+        if stdin.read_line(&mut line).unwrap_or(0) == 0 {
+            println!();
+            break; // EOF (Ctrl+D)
+        }
+        let line = line.trim_end_matches('\n');
+
+        if buffer.is_empty() {
+            if let Some(command) = line.trim().strip_prefix(':') {
+                if handle_repl_command(command.trim(), &mut mode) {
+                    break;
                 }
-                println!("Generated Babel plugin: {:?}", babel_path);
+                continue;
             }
+            if line.trim().is_empty() {
+                continue;
+            }
+        }
 
-            if let Some(swc_code) = generated.swc {
-                let swc_path = output.join("lib.rs");
-                if let Err(e) = fs::write(&swc_path, swc_code) {
-                    eprintln!("Error writing SWC output: {}", e);
-                    std::process::exit(1);
+        if !buffer.is_empty() {
+            buffer.push('\n');
+        }
+        buffer.push_str(line);
+
+        if !repl_input_complete(&buffer) {
+            // Unclosed braces/brackets/parens - keep showing the
+            // continuation prompt instead of reporting a parse error.
+            continue;
+        }
+
+        evaluate_repl_fragment(&buffer, mode);
+        append_repl_history(&history_path, &buffer);
+        buffer.clear();
+    }
+}
+
+fn handle_repl_command(command: &str, mode: &mut ReplMode) -> bool {
+    let mut parts = command.split_whitespace();
+    match parts.next() {
+        Some("quit") | Some("q") => return true,
+        Some("help") => {
+            println!("  :mode tokens|ast|check|babel|swc   set what gets echoed after each fragment");
+            println!("  :quit                              exit the REPL");
+        }
+        Some("mode") => match parts.next() {
+            Some(value) => match value.parse::<ReplMode>() {
+                Ok(parsed) => {
+                    *mode = parsed;
+                    println!("mode set to {}", mode.name());
                 }
-                println!("Generated SWC plugin: {:?}", swc_path);
+                Err(e) => eprintln!("{}", e),
+            },
+            None => eprintln!("Usage: :mode tokens|ast|check|babel|swc (current: {})", mode.name()),
+        },
+        Some(other) => eprintln!("Unknown command: :{}", other),
+        None => {}
+    }
+    false
+}
+
+/// A fragment is ready to parse once every brace/paren/bracket it opened has
+/// been closed. An unmatched *closer* (a genuine syntax error rather than
+/// unfinished input) is left for the parser itself to report.
+fn repl_input_complete(source: &str) -> bool {
+    let mut lexer = Lexer::new(source);
+    let tokens = lexer.tokenize();
+    let mut depth: i32 = 0;
+    for token in &tokens {
+        match token.kind {
+            TokenKind::LBrace | TokenKind::LParen | TokenKind::LBracket => depth += 1,
+            TokenKind::RBrace | TokenKind::RParen | TokenKind::RBracket => depth -= 1,
+            _ => {}
+        }
+    }
+    depth <= 0
+}
+
+fn evaluate_repl_fragment(source: &str, mode: ReplMode) {
+    let mut lexer = Lexer::new(source);
+    let tokens = lexer.tokenize();
+
+    if mode == ReplMode::Tokens {
+        for token in &tokens {
+            println!("{:>4}:{:<3} {:?}", token.span.line, token.span.column, token.kind);
+        }
+        return;
+    }
+
+    let mut parser = Parser::new(tokens);
+    let program = match parser.parse() {
+        Ok(program) => program,
+        Err(e) => {
+            render_diagnostics(source, &mut [parse_error_diagnostic(&e)]);
+            return;
+        }
+    };
+
+    if mode == ReplMode::Ast {
+        println!("{:#?}", program);
+        return;
+    }
+
+    let result = analyze(&program);
+    render_diagnostics(source, &mut analysis_diagnostics(&result));
+
+    if mode == ReplMode::Check {
+        if result.errors.is_empty() {
+            println!("ok");
+        }
+        return;
+    }
+
+    if !result.errors.is_empty() {
+        return;
+    }
+
+    repl_codegen_output(program, mode);
+}
+
+#[cfg(feature = "codegen")]
+fn repl_codegen_output(mut program: rustscript::Program, mode: ReplMode) {
+    lower(&mut program);
+
+    let target = match mode {
+        ReplMode::Babel => Target::Babel,
+        ReplMode::Swc => Target::Swc,
+        _ => return,
+    };
+
+    let generated = generate(&program, target);
+    match mode {
+        ReplMode::Babel => {
+            if let Some(code) = generated.babel {
+                println!("{}", code);
+            }
+        }
+        ReplMode::Swc => {
+            if let Some(code) = generated.swc {
+                println!("{}", code);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(not(feature = "codegen"))]
+fn repl_codegen_output(_program: rustscript::Program, mode: ReplMode) {
+    if matches!(mode, ReplMode::Babel | ReplMode::Swc) {
+        eprintln!("This build was compiled without the `codegen` feature");
+    }
+}
+
+fn append_repl_history(history_path: &PathBuf, fragment: &str) {
+    use std::io::Write;
+
+    let file = std::fs::OpenOptions::new().create(true).append(true).open(history_path);
+    match file {
+        Ok(mut file) => {
+            let _ = writeln!(file, "{}\n---", fragment);
+        }
+        Err(e) => eprintln!("Warning: could not write REPL history: {}", e),
+    }
+}
+
+/// Outcome of a single check/build pass, used both for the one-shot CLI exit
+/// code and for the per-rebuild summary printed in `--watch` mode.
+struct WatchOutcome {
+    success: bool,
+    error_count: usize,
+    warning_count: usize,
+    /// Dependency files discovered during this pass that should also be
+    /// watched (e.g. imported modules). RustScript's `use` resolution
+    /// currently only tracks imported names, not the files they came from,
+    /// so this is always empty until module-path resolution lands.
+    extra_watch_paths: Vec<PathBuf>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum DiagnosticFormat {
+    Human,
+    Json,
+}
+
+impl std::str::FromStr for DiagnosticFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(DiagnosticFormat::Human),
+            "json" => Ok(DiagnosticFormat::Json),
+            other => Err(format!("Unknown format: {}. Use 'human' or 'json'", other)),
+        }
+    }
+}
+
+/// Render `diagnostics` in whichever format the CLI was asked for, falling
+/// back to human output (with a warning) if `format` doesn't parse.
+fn emit_diagnostics(file: &PathBuf, source: &str, format: &str, diagnostics: &mut [Diagnostic]) {
+    match format.parse::<DiagnosticFormat>() {
+        Ok(DiagnosticFormat::Json) => render_diagnostics_json(&file.display().to_string(), diagnostics),
+        Ok(DiagnosticFormat::Human) => render_diagnostics(source, diagnostics),
+        Err(e) => {
+            eprintln!("{}", e);
+            render_diagnostics(source, diagnostics);
+        }
+    }
+}
+
+fn parse_error_diagnostic(error: &rustscript::ParseError) -> Diagnostic {
+    Diagnostic {
+        severity: Severity::Error,
+        code: "parse-error".to_string(),
+        message: error.message.to_string(),
+        line: error.span.line,
+        column: error.span.column,
+        hint: error.suggestion.as_ref().map(|(hint, _)| hint.to_string()),
+    }
+}
+
+fn analysis_diagnostics(result: &rustscript::AnalysisResult) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for error in &result.errors {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            code: error.code.to_string(),
+            message: error.message.to_string(),
+            line: error.span.line,
+            column: error.span.column,
+            hint: error.hint.as_ref().map(|hint| hint.to_string()),
+        });
+    }
+    for warning in &result.warnings {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Warning,
+            code: warning.code.to_string(),
+            message: warning.message.to_string(),
+            line: warning.span.line,
+            column: warning.span.column,
+            hint: warning.hint.as_ref().map(|hint| hint.to_string()),
+        });
+    }
+    diagnostics
+}
+
+fn run_check(file: &PathBuf, format: &str, quiet: bool) -> WatchOutcome {
+    let source = match fs::read_to_string(file) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Error reading file: {}", e);
+            return WatchOutcome { success: false, error_count: 1, warning_count: 0, extra_watch_paths: Vec::new() };
+        }
+    };
+
+    let mut lexer = Lexer::new(&source);
+    let tokens = lexer.tokenize();
+    let mut parser = Parser::new(tokens);
+
+    let program = match parser.parse() {
+        Ok(p) => p,
+        Err(e) => {
+            emit_diagnostics(file, &source, format, &mut [parse_error_diagnostic(&e)]);
+            return WatchOutcome { success: false, error_count: 1, warning_count: 0, extra_watch_paths: Vec::new() };
+        }
+    };
+
+    let result = analyze(&program);
+    emit_diagnostics(file, &source, format, &mut analysis_diagnostics(&result));
+
+    if result.errors.is_empty() {
+        if !quiet {
+            println!("Check passed: {:?}", file);
+        }
+    } else {
+        eprintln!("Check failed: {} error(s)", result.errors.len());
+    }
+
+    WatchOutcome {
+        success: result.errors.is_empty(),
+        error_count: result.errors.len(),
+        warning_count: result.warnings.len(),
+        extra_watch_paths: Vec::new(),
+    }
+}
+
+#[cfg(feature = "codegen")]
+fn run_build(file: &PathBuf, target: &str, output: &PathBuf, format: &str, quiet: bool) -> WatchOutcome {
+    let failed = |error_count| WatchOutcome {
+        success: false,
+        error_count,
+        warning_count: 0,
+        extra_watch_paths: Vec::new(),
+    };
+
+    let source = match fs::read_to_string(file) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Error reading file: {}", e);
+            return failed(1);
+        }
+    };
+
+    let mut lexer = Lexer::new(&source);
+    let tokens = lexer.tokenize();
+    let mut parser = Parser::new(tokens);
+
+    let mut program = match parser.parse() {
+        Ok(p) => p,
+        Err(e) => {
+            emit_diagnostics(file, &source, format, &mut [parse_error_diagnostic(&e)]);
+            return failed(1);
+        }
+    };
+
+    let result = analyze(&program);
+    emit_diagnostics(file, &source, format, &mut analysis_diagnostics(&result));
+    if !result.errors.is_empty() {
+        eprintln!("Build failed: {} error(s)", result.errors.len());
+        return failed(result.errors.len());
+    }
+
+    lower(&mut program);
+
+    let target_enum = match target {
+        "babel" => Target::Babel,
+        "swc" => Target::Swc,
+        "both" => Target::Both,
+        _ => {
+            eprintln!("Unknown target: {}. Use 'babel', 'swc', or 'both'", target);
+            return failed(1);
+        }
+    };
+
+    let generated = generate(&program, target_enum);
+
+    if let Err(e) = fs::create_dir_all(output) {
+        eprintln!("Error creating output directory: {}", e);
+        return failed(1);
+    }
+
+    if let Some(babel_code) = generated.babel {
+        let babel_path = output.join("index.js");
+        if let Err(e) = fs::write(&babel_path, babel_code) {
+            eprintln!("Error writing Babel output: {}", e);
+            return failed(1);
+        }
+        if !quiet {
+            println!("Generated Babel plugin: {:?}", babel_path);
+        }
+    }
+
+    if let Some(swc_code) = generated.swc {
+        let swc_path = output.join("lib.rs");
+        if let Err(e) = fs::write(&swc_path, swc_code) {
+            eprintln!("Error writing SWC output: {}", e);
+            return failed(1);
+        }
+        if !quiet {
+            println!("Generated SWC plugin: {:?}", swc_path);
+        }
+    }
+
+    if !quiet {
+        println!("Build complete!");
+    }
+
+    WatchOutcome {
+        success: true,
+        error_count: 0,
+        warning_count: result.warnings.len(),
+        extra_watch_paths: Vec::new(),
+    }
+}
+
+/// Re-run `rebuild` whenever any watched file changes on disk. Changes are
+/// debounced so a burst of saves (formatters, editors writing temp files
+/// first) only triggers one rebuild, and the watch set is refreshed after
+/// every pass so newly-discovered dependencies start being tracked too.
+fn watch_loop<F>(file: &PathBuf, mut rebuild: F) -> !
+where
+    F: FnMut(&PathBuf) -> WatchOutcome,
+{
+    const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(200);
+    const DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(300);
+
+    let mut watched = std::collections::HashMap::new();
+    let outcome = rebuild(file);
+    print_watch_summary(&outcome);
+    refresh_watch_set(&mut watched, file, &outcome.extra_watch_paths);
+
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+        if poll_changed_paths(&mut watched).is_empty() {
+            continue;
+        }
+
+        // Debounce: keep draining changes until the filesystem is quiet.
+        loop {
+            std::thread::sleep(DEBOUNCE);
+            if poll_changed_paths(&mut watched).is_empty() {
+                break;
             }
+        }
+
+        clear_terminal();
+        let outcome = rebuild(file);
+        print_watch_summary(&outcome);
+        refresh_watch_set(&mut watched, file, &outcome.extra_watch_paths);
+    }
+}
+
+fn print_watch_summary(outcome: &WatchOutcome) {
+    if outcome.success {
+        print!("Build passed");
+    } else {
+        print!("Build failed");
+    }
+    if outcome.error_count > 0 {
+        print!(" - {} error(s)", outcome.error_count);
+    }
+    if outcome.warning_count > 0 {
+        print!(" - {} warning(s)", outcome.warning_count);
+    }
+    println!();
+    println!("Watching for changes... (Ctrl+C to stop)");
+}
+
+fn is_watchable_source(path: &std::path::Path) -> bool {
+    path.extension().and_then(|ext| ext.to_str()) == Some("rs")
+}
+
+fn refresh_watch_set(
+    watched: &mut std::collections::HashMap<PathBuf, std::time::SystemTime>,
+    file: &PathBuf,
+    extra: &[PathBuf],
+) {
+    watched.clear();
+    for path in std::iter::once(file.clone()).chain(extra.iter().cloned()) {
+        if !is_watchable_source(&path) {
+            continue;
+        }
+        if let Ok(modified) = fs::metadata(&path).and_then(|meta| meta.modified()) {
+            watched.insert(path, modified);
+        }
+    }
+}
 
-            println!("Build complete!");
+fn poll_changed_paths(
+    watched: &mut std::collections::HashMap<PathBuf, std::time::SystemTime>,
+) -> Vec<PathBuf> {
+    let mut changed = Vec::new();
+    for (path, last_modified) in watched.iter_mut() {
+        if let Ok(modified) = fs::metadata(path).and_then(|meta| meta.modified()) {
+            if modified > *last_modified {
+                *last_modified = modified;
+                changed.push(path.clone());
+            }
         }
     }
+    changed
+}
+
+fn clear_terminal() {
+    print!("\x1B[2J\x1B[1;1H");
 }
@@ -7,6 +7,91 @@ use crate::parser::ast::*;
 pub struct Parser {
     tokens: Vec<Token>,
     pos: usize,
+    /// Errors collected in recovering mode (see `parse_recovering`)
+    errors: Vec<ParseError>,
+    /// When set, statement-level parse errors are pushed onto `errors` and
+    /// recovered from via `synchronize()` instead of aborting the parse.
+    /// Set by `parse_recovering`.
+    recovering: bool,
+    /// Label (if any) and kind of each loop currently being parsed, innermost
+    /// last - consulted when parsing `break`/`continue` to validate their
+    /// label and whether a value is allowed.
+    loop_stack: Vec<(Option<String>, LoopKind)>,
+}
+
+/// A saved cursor position, used to backtrack after a speculative parse.
+struct ParserSnapshot {
+    pos: usize,
+}
+
+/// Contextual restrictions on what an expression parse is allowed to consume,
+/// threaded through the Pratt engine instead of duplicating the whole
+/// precedence cascade per restriction (e.g. a separate `_no_struct` chain).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Restrictions(u8);
+
+impl Restrictions {
+    pub const NONE: Restrictions = Restrictions(0);
+    /// Don't let a bare identifier followed by `{` be parsed as a struct
+    /// literal - used in `for`/`while`/`if`/`match` headers, where `{` starts
+    /// the body.
+    pub const NO_STRUCT_LITERAL: Restrictions = Restrictions(1 << 0);
+    /// Don't let a trailing `(...)` after a parsed primary be parsed as a
+    /// call. A call written directly on an identifier (`foo(...)`) is still
+    /// parsed as one by `parse_primary`.
+    pub const NO_CALL: Restrictions = Restrictions(1 << 1);
+
+    fn contains(self, other: Restrictions) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl std::ops::BitOr for Restrictions {
+    type Output = Restrictions;
+
+    fn bitor(self, rhs: Restrictions) -> Restrictions {
+        Restrictions(self.0 | rhs.0)
+    }
+}
+
+/// Associativity of a binary operator in the Pratt precedence table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Fixity {
+    Left,
+    Right,
+}
+
+/// How a closure captures the variables it references from its enclosing
+/// scope, paralleling the `&`/`&mut` distinction in a `traverse` block's
+/// `capturing [...]` clause - except a closure's capture mode applies to the
+/// whole closure rather than per-variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CaptureMode {
+    /// Default: capture by reference, like an unadorned `capturing [&x]`.
+    Ref,
+    /// `move |...| ...`: capture by value.
+    Move,
+}
+
+/// The kind of an enclosing loop, tracked on `Parser::loop_stack` so
+/// `break`/`continue` can validate their label (if any) against the loops
+/// currently being parsed, and so `break <value>` can be rejected outside a
+/// `loop` (only `loop` may yield a value; `while`/`for` always yield `()`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LoopKind {
+    Loop,
+    While,
+    For,
+}
+
+/// How confidently a suggested fix can be applied automatically.
+/// Mirrors rustc's `Applicability`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Applicability {
+    /// The suggestion is guaranteed to be correct and can be applied mechanically.
+    MachineApplicable,
+    /// The suggestion is likely correct but should be reviewed before applying.
+    MaybeIncorrect,
 }
 
 /// Parse error
@@ -14,6 +99,9 @@ pub struct Parser {
 pub struct ParseError {
     pub message: String,
     pub span: Span,
+    /// An optional actionable fix: replacement text and the span it replaces.
+    pub suggestion: Option<(String, Span)>,
+    pub applicability: Applicability,
 }
 
 impl ParseError {
@@ -21,19 +109,47 @@ impl ParseError {
         Self {
             message: message.into(),
             span,
+            suggestion: None,
+            applicability: Applicability::MaybeIncorrect,
         }
     }
+
+    /// Attach a machine-applicable or maybe-incorrect fix suggestion to this error.
+    pub fn with_suggestion(
+        mut self,
+        suggestion: impl Into<String>,
+        span: Span,
+        applicability: Applicability,
+    ) -> Self {
+        self.suggestion = Some((suggestion.into(), span));
+        self.applicability = applicability;
+        self
+    }
 }
 
 pub type ParseResult<T> = Result<T, ParseError>;
 
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, pos: 0 }
+        Self { tokens, pos: 0, errors: Vec::new(), recovering: false, loop_stack: Vec::new() }
+    }
+
+    /// Save the current cursor position so a speculative parse can be undone
+    /// with `restore`.
+    #[allow(dead_code)]
+    fn snapshot(&self) -> ParserSnapshot {
+        ParserSnapshot { pos: self.pos }
+    }
+
+    /// Rewind the cursor to a previously taken `snapshot`.
+    #[allow(dead_code)]
+    fn restore(&mut self, snapshot: ParserSnapshot) {
+        self.pos = snapshot.pos;
     }
 
     /// Parse a complete program
     pub fn parse(&mut self) -> ParseResult<Program> {
+        self.recovering = false;
         self.skip_newlines();
         let start_span = self.current_span();
 
@@ -60,6 +176,139 @@ impl Parser {
         })
     }
 
+    /// Parse a complete program in recovering mode: instead of bailing at the first
+    /// `ParseError`, record it and synchronize to the next reliable boundary so the
+    /// rest of the file still gets parsed. Returns every error collected along the way.
+    pub fn parse_recovering(&mut self) -> Result<Program, Vec<ParseError>> {
+        self.recovering = true;
+        self.errors.clear();
+        self.skip_newlines();
+        let start_span = self.current_span();
+
+        let mut uses = Vec::new();
+        while self.check(TokenKind::Use) {
+            match self.parse_use_stmt() {
+                Ok(use_stmt) => uses.push(use_stmt),
+                Err(e) => {
+                    self.errors.push(e);
+                    self.synchronize();
+                }
+            }
+            self.skip_newlines();
+        }
+
+        let decl = if self.check(TokenKind::Plugin) {
+            self.parse_plugin().map(TopLevelDecl::Plugin)
+        } else if self.check(TokenKind::Writer) {
+            self.parse_writer().map(TopLevelDecl::Writer)
+        } else {
+            self.parse_module().map(TopLevelDecl::Module)
+        };
+
+        let decl = match decl {
+            Ok(decl) => decl,
+            Err(e) => {
+                self.errors.push(e);
+                TopLevelDecl::Module(ModuleDecl {
+                    items: Vec::new(),
+                    span: start_span,
+                })
+            }
+        };
+
+        if self.errors.is_empty() {
+            Ok(Program {
+                uses,
+                decl,
+                span: start_span,
+            })
+        } else {
+            Err(std::mem::take(&mut self.errors))
+        }
+    }
+
+    /// In recovering mode, turn a failed expression parse into an
+    /// `Expr::Error` sentinel instead of aborting the surrounding
+    /// call/struct-init/primary: the error is recorded and the cursor is
+    /// skipped to the next expression-level boundary via `synchronize_expr`.
+    /// Outside recovering mode the error is simply forwarded, matching every
+    /// other helper in the parser.
+    fn recover_expr(&mut self, result: ParseResult<Expr>) -> ParseResult<Expr> {
+        match result {
+            Ok(expr) => Ok(expr),
+            Err(e) if self.recovering => {
+                let span = e.span;
+                self.errors.push(e);
+                self.synchronize_expr();
+                Ok(Expr::Error(span))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Skip tokens until the next expression-level boundary: a `,` or
+    /// closing `)`/`]`/`}` (left for the caller to consume/balance), a
+    /// `Newline`, or a leading statement/item keyword. Narrower than
+    /// `synchronize()` - it never consumes the boundary itself, since a
+    /// broken call argument or struct-init field still needs its enclosing
+    /// list to see the delimiter that ends it.
+    fn synchronize_expr(&mut self) {
+        while !self.is_at_end() {
+            match self.peek().map(|t| &t.kind) {
+                Some(
+                    TokenKind::Comma
+                    | TokenKind::RParen
+                    | TokenKind::RBracket
+                    | TokenKind::RBrace
+                    | TokenKind::Newline
+                    | TokenKind::Fn
+                    | TokenKind::Struct
+                    | TokenKind::Enum
+                    | TokenKind::Impl
+                    | TokenKind::Let
+                    | TokenKind::For,
+                ) => return,
+                _ => self.advance(),
+            };
+        }
+    }
+
+    /// Skip tokens until a reliable synchronization point: the next `;`, newline,
+    /// a leading statement/item keyword (`fn`/`struct`/`enum`/`impl`/`let`/`for`),
+    /// or the next `}` (brace nesting is not tracked). Modeled on rustc's panic-mode recovery in
+    /// `libsyntax`.
+    fn synchronize(&mut self) {
+        if self.check(TokenKind::Semicolon) {
+            self.advance();
+            return;
+        }
+
+        while !self.is_at_end() {
+            match self.peek().map(|t| &t.kind) {
+                Some(TokenKind::Semicolon) => {
+                    self.advance();
+                    return;
+                }
+                Some(TokenKind::RBrace) => {
+                    self.advance();
+                    return;
+                }
+                Some(
+                    TokenKind::Newline
+                    | TokenKind::Fn
+                    | TokenKind::Struct
+                    | TokenKind::Enum
+                    | TokenKind::Impl
+                    | TokenKind::Let
+                    | TokenKind::For,
+                ) => return,
+                _ => {
+                    self.advance();
+                }
+            }
+        }
+    }
+
     /// Parse use statement: `use fs;` or `use "./helpers.rsc";` or `use "./helpers.rsc" as h { foo, bar };`
     fn parse_use_stmt(&mut self) -> ParseResult<UseStmt> {
         let start_span = self.current_span();
@@ -88,24 +337,121 @@ impl Parser {
             None
         };
 
-        // Optional: { imports }
-        let imports = if self.check(TokenKind::LBrace) {
-            self.parse_import_list()?
+        // Optional: { imports } - parsed as a (possibly nested) use-tree; `imports` is
+        // kept as a flattened view for callers that only need leaf names.
+        let (imports, tree) = if self.check(TokenKind::LBrace) {
+            let children = self.parse_use_tree_group()?;
+            let mut imports = Vec::new();
+            for child in &children {
+                Self::flatten_use_tree(child, &mut imports);
+            }
+            let tree = UseTree {
+                prefix: vec![],
+                kind: UseTreeKind::Nested(children),
+                span: start_span,
+            };
+            (imports, Some(tree))
         } else {
-            vec![]
+            (vec![], None)
         };
 
-        self.expect(TokenKind::Semicolon)?;
+        self.expect_semi()?;
 
         Ok(UseStmt {
             path,
             alias,
             imports,
+            tree,
             span: start_span,
         })
     }
 
+    /// Parse a single use-tree, following rustc's `UseTree`/`UseTreeKind`: a path of
+    /// `::`-separated segments ending in a nested group (`foo::{bar, baz::{qux}}`),
+    /// a glob (`foo::*`), or a leaf with an optional `as` rename.
+    fn parse_use_tree(&mut self) -> ParseResult<UseTree> {
+        let start_span = self.current_span();
+        let mut prefix = Vec::new();
+
+        loop {
+            if self.check(TokenKind::Star) {
+                self.advance();
+                return Ok(UseTree {
+                    prefix,
+                    kind: UseTreeKind::Glob,
+                    span: start_span,
+                });
+            }
+
+            if self.check(TokenKind::LBrace) {
+                let children = self.parse_use_tree_group()?;
+                return Ok(UseTree {
+                    prefix,
+                    kind: UseTreeKind::Nested(children),
+                    span: start_span,
+                });
+            }
+
+            let segment = self.expect_ident()?;
+
+            if self.match_token(TokenKind::ColonColon) {
+                prefix.push(segment);
+                continue;
+            }
+
+            let alias = if self.match_token(TokenKind::As) {
+                Some(self.expect_ident()?)
+            } else {
+                None
+            };
+
+            return Ok(UseTree {
+                prefix,
+                kind: UseTreeKind::Simple { name: segment, alias },
+                span: start_span,
+            });
+        }
+    }
+
+    /// Parse a nested use-tree group: `{ tree, tree, ... }`
+    fn parse_use_tree_group(&mut self) -> ParseResult<Vec<UseTree>> {
+        self.expect(TokenKind::LBrace)?;
+        self.skip_newlines();
+        let mut trees = Vec::new();
+
+        loop {
+            if self.check(TokenKind::RBrace) {
+                break;
+            }
+            trees.push(self.parse_use_tree()?);
+            self.skip_newlines();
+            if !self.match_token(TokenKind::Comma) {
+                break;
+            }
+            self.skip_newlines();
+        }
+
+        self.expect(TokenKind::RBrace)?;
+        Ok(trees)
+    }
+
+    /// Flatten a use-tree into leaf names, for the legacy flat `imports` list.
+    fn flatten_use_tree(tree: &UseTree, out: &mut Vec<String>) {
+        match &tree.kind {
+            UseTreeKind::Simple { name, alias } => {
+                out.push(alias.clone().unwrap_or_else(|| name.clone()))
+            }
+            UseTreeKind::Nested(children) => {
+                for child in children {
+                    Self::flatten_use_tree(child, out);
+                }
+            }
+            UseTreeKind::Glob => out.push("*".to_string()),
+        }
+    }
+
     /// Parse import list: `{ foo, bar, baz }`
+    #[allow(dead_code)]
     fn parse_import_list(&mut self) -> ParseResult<Vec<String>> {
         self.expect(TokenKind::LBrace)?;
         self.skip_newlines();  // Allow newlines after {
@@ -180,6 +526,68 @@ impl Parser {
         })
     }
 
+    /// Parse zero or more outer attributes: `#[derive(Foo)]`, `#[deprecated]`, ...
+    /// Modeled on rustc's attribute parsing. The token-tree argument, if any, is
+    /// captured verbatim (balanced-delimiter tracking) for the plugin/writer
+    /// backend to interpret later.
+    fn parse_outer_attrs(&mut self) -> ParseResult<Vec<Attribute>> {
+        let mut attrs = Vec::new();
+
+        loop {
+            self.skip_newlines();
+            if !self.check(TokenKind::Hash) {
+                break;
+            }
+            self.advance();
+            self.expect(TokenKind::LBracket)?;
+            let path = self.expect_ident()?;
+
+            let args = if self.check(TokenKind::LParen) {
+                self.parse_attr_token_tree()?
+            } else {
+                Vec::new()
+            };
+
+            self.expect(TokenKind::RBracket)?;
+            attrs.push(Attribute { path, args });
+        }
+
+        Ok(attrs)
+    }
+
+    /// Collect a parenthesized token tree verbatim, tracking balanced `(`/`)`
+    /// so nested content inside an attribute's arguments is captured without
+    /// being interpreted here.
+    fn parse_attr_token_tree(&mut self) -> ParseResult<Vec<Token>> {
+        self.expect(TokenKind::LParen)?;
+        let mut tokens = Vec::new();
+        let mut depth = 1;
+
+        loop {
+            let tok = self
+                .peek()
+                .cloned()
+                .ok_or_else(|| self.error("Unterminated attribute argument list"))?;
+
+            match tok.kind {
+                TokenKind::LParen => depth += 1,
+                TokenKind::RParen => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.advance();
+                        break;
+                    }
+                }
+                _ => {}
+            }
+
+            tokens.push(tok);
+            self.advance();
+        }
+
+        Ok(tokens)
+    }
+
     /// Parse plugin/writer body items
     fn parse_plugin_body(&mut self) -> ParseResult<Vec<PluginItem>> {
         let mut items = Vec::new();
@@ -191,16 +599,30 @@ impl Parser {
                 break;
             }
 
+            let attrs = self.parse_outer_attrs()?;
+
             let item = if self.check(TokenKind::Struct) {
-                PluginItem::Struct(self.parse_struct()?)
+                let mut decl = self.parse_struct()?;
+                decl.attrs = attrs;
+                PluginItem::Struct(decl)
             } else if self.check(TokenKind::Enum) {
-                PluginItem::Enum(self.parse_enum()?)
+                let mut decl = self.parse_enum()?;
+                decl.attrs = attrs;
+                PluginItem::Enum(decl)
             } else if self.check(TokenKind::Fn) || self.check(TokenKind::Pub) {
-                PluginItem::Function(self.parse_function()?)
+                let mut decl = self.parse_function()?;
+                decl.attrs = attrs;
+                PluginItem::Function(decl)
             } else if self.check(TokenKind::Impl) {
-                PluginItem::Impl(self.parse_impl()?)
+                let mut decl = self.parse_impl()?;
+                decl.attrs = attrs;
+                PluginItem::Impl(decl)
+            } else if self.check(TokenKind::Trait) {
+                let mut decl = self.parse_trait()?;
+                decl.attrs = attrs;
+                PluginItem::Trait(decl)
             } else {
-                return Err(self.error("Expected struct, enum, fn, or impl"));
+                return Err(self.error("Expected struct, enum, fn, trait, or impl"));
             };
 
             items.push(item);
@@ -209,11 +631,78 @@ impl Parser {
         Ok(items)
     }
 
+    /// Parse generic parameters: `<T, U: Bound + Bound2>`. Returns an empty list
+    /// if the next token isn't `<`.
+    fn parse_generics(&mut self) -> ParseResult<Vec<GenericParam>> {
+        let mut params = Vec::new();
+
+        if !self.match_token(TokenKind::Lt) {
+            return Ok(params);
+        }
+
+        loop {
+            let name = self.expect_ident()?;
+
+            let mut bounds = Vec::new();
+            if self.match_token(TokenKind::Colon) {
+                bounds.push(self.parse_type()?);
+                while self.match_token(TokenKind::Plus) {
+                    bounds.push(self.parse_type()?);
+                }
+            }
+
+            params.push(GenericParam { name, bounds });
+
+            if !self.match_token(TokenKind::Comma) {
+                break;
+            }
+            // Allow trailing comma before `>`
+            if self.check(TokenKind::Gt) {
+                break;
+            }
+        }
+
+        self.expect(TokenKind::Gt)?;
+        Ok(params)
+    }
+
+    /// Parse an optional `where T: Bound, U: Bound` clause.
+    fn parse_where_clause(&mut self) -> ParseResult<Vec<WherePredicate>> {
+        let mut predicates = Vec::new();
+
+        if !self.match_token(TokenKind::Where) {
+            return Ok(predicates);
+        }
+
+        loop {
+            let ty = self.parse_type()?;
+            self.expect(TokenKind::Colon)?;
+            let mut bounds = Vec::new();
+            bounds.push(self.parse_type()?);
+            while self.match_token(TokenKind::Plus) {
+                bounds.push(self.parse_type()?);
+            }
+
+            predicates.push(WherePredicate { ty, bounds });
+
+            if !self.match_token(TokenKind::Comma) {
+                break;
+            }
+            if self.check(TokenKind::LBrace) {
+                break;
+            }
+        }
+
+        Ok(predicates)
+    }
+
     /// Parse struct declaration
     fn parse_struct(&mut self) -> ParseResult<StructDecl> {
         let start_span = self.current_span();
         self.expect(TokenKind::Struct)?;
         let name = self.expect_ident()?;
+        let generics = self.parse_generics()?;
+        let where_clause = self.parse_where_clause()?;
         self.expect(TokenKind::LBrace)?;
 
         let mut fields = Vec::new();
@@ -223,6 +712,7 @@ impl Parser {
                 break;
             }
 
+            let field_attrs = self.parse_outer_attrs()?;
             let field_span = self.current_span();
             let field_name = self.expect_ident()?;
             self.expect(TokenKind::Colon)?;
@@ -231,12 +721,19 @@ impl Parser {
             fields.push(StructField {
                 name: field_name,
                 ty,
+                attrs: field_attrs,
                 span: field_span,
             });
 
             self.skip_newlines();
             if !self.check(TokenKind::RBrace) {
-                self.expect(TokenKind::Comma)?;
+                if !self.check(TokenKind::Comma) {
+                    let span = self.current_span();
+                    return Err(self
+                        .error("Expected ','")
+                        .with_suggestion(",", span, Applicability::MachineApplicable));
+                }
+                self.advance();
             }
         }
 
@@ -244,7 +741,10 @@ impl Parser {
 
         Ok(StructDecl {
             name,
+            generics,
+            where_clause,
             fields,
+            attrs: Vec::new(),
             span: start_span,
         })
     }
@@ -254,6 +754,8 @@ impl Parser {
         let start_span = self.current_span();
         self.expect(TokenKind::Enum)?;
         let name = self.expect_ident()?;
+        let generics = self.parse_generics()?;
+        let where_clause = self.parse_where_clause()?;
         self.expect(TokenKind::LBrace)?;
 
         let mut variants = Vec::new();
@@ -263,6 +765,7 @@ impl Parser {
                 break;
             }
 
+            let variant_attrs = self.parse_outer_attrs()?;
             let variant_span = self.current_span();
             let variant_name = self.expect_ident()?;
 
@@ -284,6 +787,7 @@ impl Parser {
             variants.push(EnumVariant {
                 name: variant_name,
                 fields,
+                attrs: variant_attrs,
                 span: variant_span,
             });
 
@@ -297,7 +801,10 @@ impl Parser {
 
         Ok(EnumDecl {
             name,
+            generics,
+            where_clause,
             variants,
+            attrs: Vec::new(),
             span: start_span,
         })
     }
@@ -308,6 +815,7 @@ impl Parser {
         let is_pub = self.match_token(TokenKind::Pub);
         self.expect(TokenKind::Fn)?;
         let name = self.expect_ident()?;
+        let generics = self.parse_generics()?;
 
         self.expect(TokenKind::LParen)?;
         let params = self.parse_params()?;
@@ -319,14 +827,18 @@ impl Parser {
             None
         };
 
-        let body = self.parse_block()?;
+        let where_clause = self.parse_where_clause()?;
+        let body = Some(self.parse_block()?);
 
         Ok(FnDecl {
             is_pub,
             name,
+            generics,
+            where_clause,
             params,
             return_type,
             body,
+            attrs: Vec::new(),
             span: start_span,
         })
     }
@@ -402,7 +914,10 @@ impl Parser {
 
                 // Not a self parameter - this is an error
                 // RustScript doesn't support &param syntax, only param: &Type
-                return Err(self.error("Unexpected '&' - use 'param: &Type' syntax instead"));
+                let span = self.current_span();
+                return Err(self
+                    .error("Unexpected '&' - use 'param: &Type' syntax instead")
+                    .with_suggestion("param: &Type", span, Applicability::MaybeIncorrect));
             }
 
             // Regular parameter: name: Type
@@ -428,7 +943,18 @@ impl Parser {
     fn parse_impl(&mut self) -> ParseResult<ImplBlock> {
         let start_span = self.current_span();
         self.expect(TokenKind::Impl)?;
-        let target = self.expect_ident()?;
+        let generics = self.parse_generics()?;
+        let first = self.expect_ident()?;
+
+        // `impl Trait for Type { ... }` vs plain `impl Type { ... }`
+        let (trait_, target) = if self.match_token(TokenKind::For) {
+            let target = self.expect_ident()?;
+            (Some(first), target)
+        } else {
+            (None, first)
+        };
+
+        let where_clause = self.parse_where_clause()?;
         self.expect(TokenKind::LBrace)?;
 
         let mut items = Vec::new();
@@ -443,8 +969,81 @@ impl Parser {
         self.expect(TokenKind::RBrace)?;
 
         Ok(ImplBlock {
+            generics,
+            trait_,
             target,
+            where_clause,
             items,
+            attrs: Vec::new(),
+            span: start_span,
+        })
+    }
+
+    /// Parse a trait declaration: `trait Name { fn required(...); fn default(...) { ... } }`
+    fn parse_trait(&mut self) -> ParseResult<TraitDecl> {
+        let start_span = self.current_span();
+        self.expect(TokenKind::Trait)?;
+        let name = self.expect_ident()?;
+        let generics = self.parse_generics()?;
+        let where_clause = self.parse_where_clause()?;
+        self.expect(TokenKind::LBrace)?;
+
+        let mut methods = Vec::new();
+        loop {
+            self.skip_newlines();
+            if self.check(TokenKind::RBrace) {
+                break;
+            }
+            methods.push(self.parse_trait_method()?);
+        }
+
+        self.expect(TokenKind::RBrace)?;
+
+        Ok(TraitDecl {
+            name,
+            generics,
+            where_clause,
+            methods,
+            attrs: Vec::new(),
+            span: start_span,
+        })
+    }
+
+    /// Parse a trait method: a signature (`fn foo(...);`, required) or a signature
+    /// with a block body (`fn foo(...) { ... }`, a default implementation).
+    fn parse_trait_method(&mut self) -> ParseResult<FnDecl> {
+        let start_span = self.current_span();
+        self.expect(TokenKind::Fn)?;
+        let name = self.expect_ident()?;
+        let generics = self.parse_generics()?;
+
+        self.expect(TokenKind::LParen)?;
+        let params = self.parse_params()?;
+        self.expect(TokenKind::RParen)?;
+
+        let return_type = if self.match_token(TokenKind::Arrow) {
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
+
+        let where_clause = self.parse_where_clause()?;
+
+        let body = if self.match_token(TokenKind::Semicolon) {
+            None
+        } else {
+            Some(self.parse_block()?)
+        };
+
+        Ok(FnDecl {
+            is_pub: false,
+            name,
+            generics,
+            where_clause,
+            params,
+            return_type,
+            body,
+            attrs: Vec::new(),
             span: start_span,
         })
     }
@@ -531,7 +1130,15 @@ impl Parser {
             if self.check(TokenKind::RBrace) || self.is_at_end() {
                 break;
             }
-            stmts.push(self.parse_statement()?);
+            let attrs = self.parse_outer_attrs()?;
+            match self.parse_statement() {
+                Ok(stmt) => stmts.push(AttributedStmt { attrs, stmt }),
+                Err(e) if self.recovering => {
+                    self.errors.push(e);
+                    self.synchronize();
+                }
+                Err(e) => return Err(e),
+            }
         }
 
         self.expect(TokenKind::RBrace)?;
@@ -555,12 +1162,14 @@ impl Parser {
             self.parse_if_stmt()
         } else if self.check(TokenKind::Match) {
             self.parse_match_stmt()
+        } else if self.check(TokenKind::Lifetime(String::new())) {
+            self.parse_labeled_loop_stmt()
         } else if self.check(TokenKind::For) {
-            self.parse_for_stmt()
+            self.parse_for_stmt(None)
         } else if self.check(TokenKind::While) {
-            self.parse_while_stmt()
+            self.parse_while_stmt(None)
         } else if self.check(TokenKind::Loop) {
-            self.parse_loop_stmt()
+            self.parse_loop_stmt(None)
         } else if self.check(TokenKind::Return) {
             self.parse_return_stmt()
         } else if self.check(TokenKind::Break) {
@@ -591,17 +1200,48 @@ impl Parser {
 
         self.expect(TokenKind::Eq)?;
         let init = self.parse_expr()?;
-        self.expect(TokenKind::Semicolon)?;
+
+        // `let PATTERN = EXPR else { ... };` - the else block runs when the
+        // pattern doesn't match and must diverge, since otherwise the
+        // pattern's bindings would be unavailable after the statement.
+        let else_block = if self.match_token(TokenKind::Else) {
+            let block = self.parse_block()?;
+            self.check_let_else_diverges(&block)?;
+            Some(block)
+        } else {
+            None
+        };
+
+        self.expect_semi()?;
 
         Ok(Stmt::Let(LetStmt {
             mutable,
             pattern,
             ty,
             init,
+            else_block,
             span: start_span,
         }))
     }
 
+    /// `let ... else` block must diverge (end in `return`, `break`, or
+    /// `continue`) - falling through would leave the pattern's bindings
+    /// uninitialized on the path after the statement.
+    fn check_let_else_diverges(&self, block: &Block) -> ParseResult<()> {
+        let diverges = matches!(
+            block.stmts.last().map(|s| &s.stmt),
+            Some(Stmt::Return(_) | Stmt::Break(_) | Stmt::Continue(_))
+        );
+        if diverges {
+            Ok(())
+        } else {
+            Err(ParseError::new(
+                "`let ... else` block must diverge (end in `return`, `break`, or `continue`)",
+                block.span,
+            ))
+        }
+    }
+
     /// Parse const statement
     fn parse_const_stmt(&mut self) -> ParseResult<Stmt> {
         let start_span = self.current_span();
@@ -616,7 +1256,7 @@ impl Parser {
 
         self.expect(TokenKind::Eq)?;
         let init = self.parse_expr()?;
-        self.expect(TokenKind::Semicolon)?;
+        self.expect_semi()?;
 
         Ok(Stmt::Const(ConstStmt {
             name,
@@ -639,7 +1279,9 @@ impl Parser {
             (Some(pat), expr)
         } else {
             // Use parse_expr_no_struct to avoid ambiguity with block
-            (None, self.parse_expr_no_struct()?)
+            let expr = self.parse_expr_no_struct()?;
+            self.check_eq_in_condition()?;
+            (None, expr)
         };
 
         let then_branch = self.parse_block()?;
@@ -700,14 +1342,21 @@ impl Parser {
 
     /// Parse match scrutinee (expression that doesn't consume {)
     fn parse_match_scrutinee(&mut self) -> ParseResult<Expr> {
-        // Use parse_or_no_struct to avoid consuming { as struct init
-        self.parse_or_no_struct()
+        self.parse_expr_no_struct()
     }
 
     /// Parse match arm
     fn parse_match_arm(&mut self) -> ParseResult<MatchArm> {
         let start_span = self.current_span();
         let pattern = self.parse_pattern()?;
+
+        // Match guard: `pattern if expr => body`
+        let guard = if self.match_token(TokenKind::If) {
+            Some(self.parse_expr_no_struct()?)
+        } else {
+            None
+        };
+
         self.expect(TokenKind::FatArrow)?;
         let body = self.parse_expr()?;
 
@@ -717,6 +1366,7 @@ impl Parser {
 
         Ok(MatchArm {
             pattern,
+            guard,
             body,
             span: start_span,
         })
@@ -731,15 +1381,40 @@ impl Parser {
             return Ok(Pattern::Wildcard);
         }
 
-        // Check for literal
+        // Check for literal, optionally the low bound of a range pattern: `lo..hi`, `lo..=hi`
         if let Some(lit) = self.try_parse_literal() {
+            if self.check(TokenKind::DotDot) || self.check(TokenKind::DotDotEq) {
+                let inclusive = self.check(TokenKind::DotDotEq);
+                self.advance();
+                let hi = self
+                    .try_parse_literal()
+                    .ok_or_else(|| self.error("Expected upper bound literal in range pattern"))?;
+                return Ok(Pattern::Range {
+                    lo: lit,
+                    hi,
+                    inclusive,
+                });
+            }
             return Ok(Pattern::Literal(lit));
         }
 
-        // Check for tuple pattern: (a, b, c)
-        if self.check(TokenKind::LParen) {
-            self.advance();
-            let mut elements = Vec::new();
+        // Binding mode: `ref` / `mut` before an identifier binding
+        if self.check(TokenKind::Ref) || self.check(TokenKind::Mut) {
+            let is_ref = self.match_token(TokenKind::Ref);
+            let is_mut = self.match_token(TokenKind::Mut);
+            let name = self.expect_ident()?;
+            return Ok(Pattern::Binding {
+                name,
+                is_ref,
+                is_mut,
+                sub: None,
+            });
+        }
+
+        // Check for tuple pattern: (a, b, c), with an optional `..` rest element
+        if self.check(TokenKind::LParen) {
+            self.advance();
+            let mut elements = Vec::new();
 
             // Empty tuple ()
             if self.check(TokenKind::RParen) {
@@ -749,7 +1424,11 @@ impl Parser {
 
             // Parse tuple elements
             loop {
-                elements.push(self.parse_pattern()?);
+                if self.match_token(TokenKind::DotDot) {
+                    elements.push(Pattern::Rest);
+                } else {
+                    elements.push(self.parse_pattern()?);
+                }
                 if !self.match_token(TokenKind::Comma) {
                     break;
                 }
@@ -769,6 +1448,34 @@ impl Parser {
             return Ok(Pattern::Tuple(elements));
         }
 
+        // Slice/array pattern: [a, b, ..], with an optional `..` rest element
+        if self.check(TokenKind::LBracket) {
+            self.advance();
+            let mut elements = Vec::new();
+
+            if self.check(TokenKind::RBracket) {
+                self.advance();
+                return Ok(Pattern::Array(elements));
+            }
+
+            loop {
+                if self.match_token(TokenKind::DotDot) {
+                    elements.push(Pattern::Rest);
+                } else {
+                    elements.push(self.parse_pattern()?);
+                }
+                if !self.match_token(TokenKind::Comma) {
+                    break;
+                }
+                if self.check(TokenKind::RBracket) {
+                    break;
+                }
+            }
+
+            self.expect(TokenKind::RBracket)?;
+            return Ok(Pattern::Array(elements));
+        }
+
         // Identifier, struct pattern, or variant pattern
         let name = self.expect_ident()?;
 
@@ -803,6 +1510,15 @@ impl Parser {
             };
             self.expect(TokenKind::RParen)?;
             Ok(Pattern::Variant { name, inner })
+        } else if self.match_token(TokenKind::At) {
+            // Binding pattern: `name @ subpattern`, e.g. `n @ 1..=9`
+            let sub = Box::new(self.parse_pattern()?);
+            Ok(Pattern::Binding {
+                name,
+                is_ref: false,
+                is_mut: false,
+                sub: Some(sub),
+            })
         } else if self.match_token(TokenKind::Pipe) {
             // Or pattern
             let mut patterns = vec![Pattern::Ident(name)];
@@ -814,8 +1530,11 @@ impl Parser {
             }
             Ok(Pattern::Or(patterns))
         } else {
-            // Check if this is a unit variant like None
-            if name == "None" || name == "true" || name == "false" {
+            // A capitalized name with no following `(`/`{` is a unit variant
+            // (e.g. `None`, or any user-defined fieldless enum variant);
+            // anything else is a plain binding. This recognizes any
+            // user-defined enum rather than keying off specific literal names.
+            if name.starts_with(|c: char| c.is_ascii_uppercase()) {
                 Ok(Pattern::Variant { name, inner: None })
             } else {
                 Ok(Pattern::Ident(name))
@@ -824,19 +1543,28 @@ impl Parser {
     }
 
     /// Parse for statement
-    fn parse_for_stmt(&mut self) -> ParseResult<Stmt> {
+    fn parse_for_stmt(&mut self, label: Option<Label>) -> ParseResult<Stmt> {
         let start_span = self.current_span();
         self.expect(TokenKind::For)?;
 
         // Parse pattern (identifier or tuple destructuring)
         let pattern = self.parse_pattern()?;
 
-        self.expect(TokenKind::In)?;
+        if !self.match_token(TokenKind::In) {
+            let span = self.current_span();
+            return Err(ParseError::new("expected `in` after `for` pattern", span)
+                .with_suggestion("in", span, Applicability::MachineApplicable));
+        }
         // Use parse_expr_no_struct to avoid ambiguity with block
         let iter = self.parse_expr_no_struct()?;
-        let body = self.parse_block()?;
+
+        self.loop_stack.push((label.as_ref().map(|l| l.name.clone()), LoopKind::For));
+        let body = self.parse_block();
+        self.loop_stack.pop();
+        let body = body?;
 
         Ok(Stmt::For(ForStmt {
+            label,
             pattern,
             iter,
             body,
@@ -844,314 +1572,198 @@ impl Parser {
         }))
     }
 
-    /// Parse expression without allowing struct initialization
-    /// This is used in contexts where `{` starts a block, not a struct
+    /// Parse an expression that must not consume a trailing `{` as a struct
+    /// initializer - used for `for`/`while`/`if`/`match` headers where `{` starts
+    /// the body, not a struct literal.
     fn parse_expr_no_struct(&mut self) -> ParseResult<Expr> {
-        // Parse the expression but stop if we see an identifier followed by {
-        // We need to support binary operators like ==, &&, ||
-        self.parse_or_no_struct()
-    }
-
-    fn parse_or_no_struct(&mut self) -> ParseResult<Expr> {
-        let mut expr = self.parse_and_no_struct()?;
-
-        while self.match_token(TokenKind::Or) {
-            let right = self.parse_and_no_struct()?;
-            let span = self.current_span();
-            expr = Expr::Binary(BinaryExpr {
-                op: BinaryOp::Or,
-                left: Box::new(expr),
-                right: Box::new(right),
-                span,
-            });
-        }
+        self.parse_range(Restrictions::NO_STRUCT_LITERAL)
+    }
+
+    /// Parse a range expression: `a..b`, `a..=b`, and the optional-endpoint
+    /// forms `a..`, `..b`, `..=b`, and bare `..` (both bounds `None`). Sits
+    /// below every binary operator in precedence, `&&`/`||` included, so
+    /// `a + 1 .. b * 2` groups the arithmetic first and `a && b .. c` parses
+    /// as `(a && b)..c`.
+    /// Both endpoints are optional, so this peeks for the range operator
+    /// (`RangeLimits::HalfOpen` for `..`, `RangeLimits::Closed` for `..=`)
+    /// before deciding whether an end expression follows, rather than going
+    /// through the uniform `binary_op_bp` table. `at_range_end` is what lets a
+    /// trailing `..` before `)`/`]`/`}`/`,`/end-of-input parse as an
+    /// open-ended range instead of a dangling-operator error.
+    fn parse_range(&mut self, restrictions: Restrictions) -> ParseResult<Expr> {
+        let span = self.current_span();
 
-        Ok(expr)
-    }
+        let start = if self.check(TokenKind::DotDot) || self.check(TokenKind::DotDotEq) {
+            None
+        } else {
+            Some(Box::new(self.parse_expr_bp(0, restrictions)?))
+        };
 
-    fn parse_and_no_struct(&mut self) -> ParseResult<Expr> {
-        let mut expr = self.parse_equality_no_struct()?;
+        let limits = if self.match_token(TokenKind::DotDotEq) {
+            RangeLimits::Closed
+        } else if self.match_token(TokenKind::DotDot) {
+            RangeLimits::HalfOpen
+        } else {
+            return Ok(*start.expect("no range operator implies a start expression was parsed"));
+        };
 
-        while self.match_token(TokenKind::And) {
-            let right = self.parse_equality_no_struct()?;
-            let span = self.current_span();
-            expr = Expr::Binary(BinaryExpr {
-                op: BinaryOp::And,
-                left: Box::new(expr),
-                right: Box::new(right),
-                span,
-            });
-        }
+        let end = if self.at_range_end(restrictions) {
+            None
+        } else {
+            Some(Box::new(self.parse_expr_bp(0, restrictions)?))
+        };
 
-        Ok(expr)
+        Ok(Expr::Range(RangeExpr {
+            start,
+            end,
+            limits,
+            span,
+        }))
     }
 
-    fn parse_equality_no_struct(&mut self) -> ParseResult<Expr> {
-        let mut expr = self.parse_comparison_no_struct()?;
-
-        loop {
-            let op = if self.match_token(TokenKind::EqEq) {
-                BinaryOp::Eq
-            } else if self.match_token(TokenKind::NotEq) {
-                BinaryOp::NotEq
-            } else {
-                break;
-            };
-
-            let right = self.parse_comparison_no_struct()?;
+    /// Whether the current position cannot start an expression - used to
+    /// detect the missing end operand of a range (`a..`, `a..,`, `a..)`,
+    /// `a..]`, or `a..{` when a struct literal would be ambiguous).
+    fn at_range_end(&self, restrictions: Restrictions) -> bool {
+        match self.peek().map(|t| &t.kind) {
+            None => true,
+            Some(
+                TokenKind::RParen
+                | TokenKind::RBracket
+                | TokenKind::RBrace
+                | TokenKind::Comma
+                | TokenKind::Semicolon
+                | TokenKind::Newline,
+            ) => true,
+            Some(TokenKind::LBrace) if restrictions.contains(Restrictions::NO_STRUCT_LITERAL) => true,
+            _ => false,
+        }
+    }
+
+    /// A bare `=` immediately after a parsed `if`/`while` condition is almost
+    /// always a typo for the comparison operator `==` (condition expressions
+    /// don't parse through `parse_assignment`, so a stray `=` here can only be
+    /// a mistake). Report it specifically instead of the generic "expected `{`"
+    /// the block parser would otherwise raise.
+    fn check_eq_in_condition(&mut self) -> ParseResult<()> {
+        if self.check(TokenKind::Eq) {
             let span = self.current_span();
-            expr = Expr::Binary(BinaryExpr {
-                op,
-                left: Box::new(expr),
-                right: Box::new(right),
+            return Err(ParseError::new("expected `==`, found `=`", span).with_suggestion(
+                "==",
                 span,
-            });
+                Applicability::MachineApplicable,
+            ));
         }
-
-        Ok(expr)
+        Ok(())
     }
 
-    fn parse_comparison_no_struct(&mut self) -> ParseResult<Expr> {
-        let mut expr = self.parse_term_no_struct()?;
+    /// Parse while statement, including `while let PATTERN = EXPR { }`
+    fn parse_while_stmt(&mut self, label: Option<Label>) -> ParseResult<Stmt> {
+        let start_span = self.current_span();
+        self.expect(TokenKind::While)?;
 
-        loop {
-            let op = if self.match_token(TokenKind::Lt) {
-                BinaryOp::Lt
-            } else if self.match_token(TokenKind::Gt) {
-                BinaryOp::Gt
-            } else if self.match_token(TokenKind::LtEq) {
-                BinaryOp::LtEq
-            } else if self.match_token(TokenKind::GtEq) {
-                BinaryOp::GtEq
-            } else {
-                break;
-            };
+        // Check for while-let: `while let Pattern = expr`
+        let (pattern, condition) = if self.match_token(TokenKind::Let) {
+            let pat = self.parse_pattern()?;
+            self.expect(TokenKind::Eq)?;
+            // Use parse_expr_no_struct to avoid ambiguity with block
+            let expr = self.parse_expr_no_struct()?;
+            (Some(pat), expr)
+        } else {
+            // Use parse_expr_no_struct to avoid ambiguity with block
+            let expr = self.parse_expr_no_struct()?;
+            self.check_eq_in_condition()?;
+            (None, expr)
+        };
 
-            let right = self.parse_term_no_struct()?;
-            let span = self.current_span();
-            expr = Expr::Binary(BinaryExpr {
-                op,
-                left: Box::new(expr),
-                right: Box::new(right),
-                span,
-            });
-        }
+        self.loop_stack.push((label.as_ref().map(|l| l.name.clone()), LoopKind::While));
+        let body = self.parse_block();
+        self.loop_stack.pop();
+        let body = body?;
 
-        Ok(expr)
+        Ok(Stmt::While(WhileStmt {
+            label,
+            pattern,
+            condition,
+            body,
+            span: start_span,
+        }))
     }
 
-    fn parse_term_no_struct(&mut self) -> ParseResult<Expr> {
-        let mut expr = self.parse_factor_no_struct()?;
+    /// Parse loop statement
+    fn parse_loop_stmt(&mut self, label: Option<Label>) -> ParseResult<Stmt> {
+        let start_span = self.current_span();
+        self.expect(TokenKind::Loop)?;
 
-        loop {
-            let op = if self.match_token(TokenKind::Plus) {
-                BinaryOp::Add
-            } else if self.match_token(TokenKind::Minus) {
-                BinaryOp::Sub
-            } else {
-                break;
-            };
+        self.loop_stack.push((label.as_ref().map(|l| l.name.clone()), LoopKind::Loop));
+        let body = self.parse_block();
+        self.loop_stack.pop();
+        let body = body?;
 
-            let right = self.parse_factor_no_struct()?;
-            let span = self.current_span();
-            expr = Expr::Binary(BinaryExpr {
-                op,
-                left: Box::new(expr),
-                right: Box::new(right),
-                span,
-            });
-        }
-
-        Ok(expr)
+        Ok(Stmt::Loop(LoopStmt {
+            label,
+            body,
+            span: start_span,
+        }))
     }
 
-    fn parse_factor_no_struct(&mut self) -> ParseResult<Expr> {
-        let mut expr = self.parse_unary_no_struct()?;
+    /// Parse a lifetime-style loop label (`'outer: `) preceding `loop`/`while`/`for`.
+    fn parse_labeled_loop_stmt(&mut self) -> ParseResult<Stmt> {
+        let label = self.parse_optional_label()?;
 
-        loop {
-            let op = if self.match_token(TokenKind::Star) {
-                BinaryOp::Mul
-            } else if self.match_token(TokenKind::Slash) {
-                BinaryOp::Div
-            } else if self.match_token(TokenKind::Percent) {
-                BinaryOp::Mod
-            } else {
-                break;
-            };
-
-            let right = self.parse_unary_no_struct()?;
-            let span = self.current_span();
-            expr = Expr::Binary(BinaryExpr {
-                op,
-                left: Box::new(expr),
-                right: Box::new(right),
-                span,
-            });
+        if self.check(TokenKind::Loop) {
+            self.parse_loop_stmt(label)
+        } else if self.check(TokenKind::While) {
+            self.parse_while_stmt(label)
+        } else if self.check(TokenKind::For) {
+            self.parse_for_stmt(label)
+        } else {
+            Err(self.error("expected `loop`, `while`, or `for` after loop label"))
         }
-
-        Ok(expr)
     }
 
-    fn parse_unary_no_struct(&mut self) -> ParseResult<Expr> {
-        let span = self.current_span();
-
-        // Handle unary operators
-        if self.match_token(TokenKind::Not) {
-            let operand = self.parse_unary_no_struct()?;
-            return Ok(Expr::Unary(UnaryExpr {
-                op: UnaryOp::Not,
-                operand: Box::new(operand),
-                span,
-            }));
-        }
-        if self.match_token(TokenKind::Minus) {
-            let operand = self.parse_unary_no_struct()?;
-            return Ok(Expr::Unary(UnaryExpr {
-                op: UnaryOp::Neg,
-                operand: Box::new(operand),
-                span,
-            }));
+    /// Parse a loop-label definition: `'outer:`.
+    fn parse_optional_label(&mut self) -> ParseResult<Option<Label>> {
+        if !self.check(TokenKind::Lifetime(String::new())) {
+            return Ok(None);
         }
-        if self.match_token(TokenKind::Star) {
-            let operand = self.parse_unary_no_struct()?;
-            return Ok(Expr::Unary(UnaryExpr {
-                op: UnaryOp::Deref,
-                operand: Box::new(operand),
-                span,
-            }));
-        }
-        if self.match_token(TokenKind::Ampersand) {
-            let is_mut = self.match_token(TokenKind::Mut);
-            let operand = self.parse_unary_no_struct()?;
-            return Ok(Expr::Unary(UnaryExpr {
-                op: if is_mut { UnaryOp::RefMut } else { UnaryOp::Ref },
-                operand: Box::new(operand),
-                span,
-            }));
-        }
-
-        self.parse_primary_no_struct()
-    }
-
-    fn parse_primary_no_struct(&mut self) -> ParseResult<Expr> {
         let span = self.current_span();
-
-        // Handle 'self' keyword
-        if self.match_token(TokenKind::Self_) {
-            let mut expr = Expr::Ident(IdentExpr {
-                name: "self".to_string(),
-                span,
-            });
-
-            // Handle member access on self
-            loop {
-                if self.match_token(TokenKind::Dot) {
-                    let property = self.expect_ident()?;
-                    let span = self.current_span();
-                    expr = Expr::Member(MemberExpr {
-                        object: Box::new(expr),
-                        property,
-                        optional: false,
-                        computed: false,
-                        is_path: false,
-                        span,
-                    });
-                } else {
-                    break;
-                }
-            }
-
-            return Ok(expr);
-        }
-
-        // Identifier (no struct init)
-        if let Some(name) = self.try_expect_ident() {
-            // Don't check for LBrace here - just return the identifier
-            let mut expr = Expr::Ident(IdentExpr { name, span });
-
-            // Handle postfix operators (member access, calls, etc.) but not struct init
-            loop {
-                if self.match_token(TokenKind::Dot) {
-                    let property = self.expect_ident()?;
-                    let span = self.current_span();
-                    expr = Expr::Member(MemberExpr {
-                        object: Box::new(expr),
-                        property,
-                        optional: false,
-                        computed: false,
-                        is_path: false,
-                        span,
-                    });
-                } else if self.match_token(TokenKind::LParen) {
-                    let args = self.parse_args()?;
-                    self.expect(TokenKind::RParen)?;
-                    let span = self.current_span();
-                    expr = Expr::Call(CallExpr {
-                        callee: Box::new(expr),
-                        args,
-                        type_args: Vec::new(),
-                        optional: false,
-                        span,
-                    });
-                } else if self.match_token(TokenKind::LBracket) {
-                    let index = self.parse_expr()?;
-                    self.expect(TokenKind::RBracket)?;
-                    let span = self.current_span();
-                    expr = Expr::Index(IndexExpr {
-                        object: Box::new(expr),
-                        index: Box::new(index),
-                        span,
-                    });
-                } else if self.match_token(TokenKind::ColonColon) {
-                    // Path expression like fs::write or HashMap::new
-                    let method = self.expect_ident()?;
-                    let span = self.current_span();
-                    expr = Expr::Member(MemberExpr {
-                        object: Box::new(expr),
-                        property: method,
-                        optional: false,
-                        computed: false,
-                        is_path: true,
-                        span,
-                    });
-                } else {
-                    // Don't handle LBrace here - that would be struct init
-                    break;
-                }
-            }
-
-            return Ok(expr);
-        }
-
-        // For other cases, delegate to normal parse_primary
-        self.parse_primary()
+        let name = match self.peek() {
+            Some(Token { kind: TokenKind::Lifetime(name), .. }) => name.clone(),
+            _ => unreachable!("checked above"),
+        };
+        self.advance();
+        self.expect(TokenKind::Colon)?;
+        Ok(Some(Label { name, span }))
     }
 
-    /// Parse while statement
-    fn parse_while_stmt(&mut self) -> ParseResult<Stmt> {
-        let start_span = self.current_span();
-        self.expect(TokenKind::While)?;
-        // Use parse_expr_no_struct to avoid ambiguity with block
-        let condition = self.parse_expr_no_struct()?;
-        let body = self.parse_block()?;
-
-        Ok(Stmt::While(WhileStmt {
-            condition,
-            body,
-            span: start_span,
-        }))
+    /// Parse an optional `'label` reference following `break`/`continue`.
+    fn try_parse_label_ref(&mut self) -> Option<Label> {
+        if !self.check(TokenKind::Lifetime(String::new())) {
+            return None;
+        }
+        let span = self.current_span();
+        let name = match self.peek() {
+            Some(Token { kind: TokenKind::Lifetime(name), .. }) => name.clone(),
+            _ => unreachable!("checked above"),
+        };
+        self.advance();
+        Some(Label { name, span })
     }
 
-    /// Parse loop statement
-    fn parse_loop_stmt(&mut self) -> ParseResult<Stmt> {
-        let start_span = self.current_span();
-        self.expect(TokenKind::Loop)?;
-        let body = self.parse_block()?;
-
-        Ok(Stmt::Loop(LoopStmt {
-            body,
-            span: start_span,
-        }))
+    /// Look up the kind of the loop a `break`/`continue` targets: the loop
+    /// named by `label`, or the innermost enclosing loop if unlabeled.
+    /// Returns `None` if `label` names no loop currently being parsed.
+    fn lookup_loop(&self, label: &Option<Label>) -> Option<LoopKind> {
+        match label {
+            Some(label) => self
+                .loop_stack
+                .iter()
+                .rev()
+                .find(|(name, _)| name.as_deref() == Some(label.name.as_str()))
+                .map(|(_, kind)| *kind),
+            None => self.loop_stack.last().map(|(_, kind)| *kind),
+        }
     }
 
     /// Parse return statement
@@ -1165,7 +1777,7 @@ impl Parser {
             None
         };
 
-        self.expect(TokenKind::Semicolon)?;
+        self.expect_semi()?;
 
         Ok(Stmt::Return(ReturnStmt {
             value,
@@ -1173,20 +1785,63 @@ impl Parser {
         }))
     }
 
-    /// Parse break statement
+    /// Parse break statement. Accepts an optional `'label` target and an
+    /// optional value (`break 'outer 42;`) - a value is only valid when
+    /// `break` targets a `loop`, since `while`/`for` always yield `()`.
     fn parse_break_stmt(&mut self) -> ParseResult<Stmt> {
         let start_span = self.current_span();
         self.expect(TokenKind::Break)?;
-        self.expect(TokenKind::Semicolon)?;
-        Ok(Stmt::Break(BreakStmt { span: start_span }))
+        let label = self.try_parse_label_ref();
+
+        let value = if !self.check(TokenKind::Semicolon)
+            && !self.check(TokenKind::Newline)
+            && !self.check(TokenKind::RBrace)
+        {
+            Some(self.parse_expr()?)
+        } else {
+            None
+        };
+
+        match (self.lookup_loop(&label), &value) {
+            (None, _) => {
+                let span = label.as_ref().map(|l| l.span).unwrap_or(start_span);
+                let message = match &label {
+                    Some(l) => format!("`break` targets unknown loop label '{}", l.name),
+                    None => "`break` used outside of a loop".to_string(),
+                };
+                return Err(ParseError::new(message, span));
+            }
+            (Some(LoopKind::Loop), _) | (Some(_), None) => {}
+            (Some(_), Some(_)) => {
+                return Err(ParseError::new(
+                    "`break` with a value is only allowed inside `loop`, not `while`/`for`",
+                    start_span,
+                ));
+            }
+        }
+
+        self.expect_semi()?;
+        Ok(Stmt::Break(BreakStmt { label, value, span: start_span }))
     }
 
-    /// Parse continue statement
+    /// Parse continue statement. Accepts an optional `'label` target, which
+    /// must name a loop currently being parsed.
     fn parse_continue_stmt(&mut self) -> ParseResult<Stmt> {
         let start_span = self.current_span();
         self.expect(TokenKind::Continue)?;
-        self.expect(TokenKind::Semicolon)?;
-        Ok(Stmt::Continue(ContinueStmt { span: start_span }))
+        let label = self.try_parse_label_ref();
+
+        if self.lookup_loop(&label).is_none() {
+            let span = label.as_ref().map(|l| l.span).unwrap_or(start_span);
+            let message = match &label {
+                Some(l) => format!("`continue` targets unknown loop label '{}", l.name),
+                None => "`continue` used outside of a loop".to_string(),
+            };
+            return Err(ParseError::new(message, span));
+        }
+
+        self.expect_semi()?;
+        Ok(Stmt::Continue(ContinueStmt { label, span: start_span }))
     }
 
     /// Parse traverse statement
@@ -1208,7 +1863,7 @@ impl Parser {
         let kind = if self.match_token(TokenKind::Using) {
             // Delegated traversal: `traverse(node) using OtherVisitor;`
             let visitor_name = self.expect_ident()?;
-            self.expect(TokenKind::Semicolon)?;
+            self.expect_semi()?;
             TraverseKind::Delegated(visitor_name)
         } else {
             // Inline traversal: `traverse(node) { ... }`
@@ -1240,7 +1895,7 @@ impl Parser {
 
                     self.expect(TokenKind::Eq)?;
                     let init = self.parse_expr()?;
-                    self.expect(TokenKind::Semicolon)?;
+                    self.expect_semi()?;
 
                     state.push(LetStmt {
                         mutable,
@@ -1321,216 +1976,132 @@ impl Parser {
         // This allows the expression to serve as the block's return value
         self.skip_newlines();
         if !self.check(TokenKind::RBrace) {
-            self.expect(TokenKind::Semicolon)?;
+            self.expect_semi()?;
         } else {
             // Try to consume semicolon if present, but don't require it
             self.match_token(TokenKind::Semicolon);
         }
 
-        Ok(Stmt::Expr(ExprStmt {
-            expr,
-            span: start_span,
-        }))
-    }
-
-    /// Parse expression (entry point for expression parsing)
-    fn parse_expr(&mut self) -> ParseResult<Expr> {
-        self.parse_assignment()
-    }
-
-    /// Parse assignment expression
-    fn parse_assignment(&mut self) -> ParseResult<Expr> {
-        let expr = self.parse_or()?;
-
-        if self.match_token(TokenKind::Eq) {
-            let value = self.parse_assignment()?;
-            let span = self.current_span();
-            return Ok(Expr::Assign(AssignExpr {
-                target: Box::new(expr),
-                value: Box::new(value),
-                span,
-            }));
-        }
-
-        // Compound assignment
-        let op = if self.match_token(TokenKind::PlusEq) {
-            Some(CompoundAssignOp::AddAssign)
-        } else if self.match_token(TokenKind::MinusEq) {
-            Some(CompoundAssignOp::SubAssign)
-        } else if self.match_token(TokenKind::StarEq) {
-            Some(CompoundAssignOp::MulAssign)
-        } else if self.match_token(TokenKind::SlashEq) {
-            Some(CompoundAssignOp::DivAssign)
-        } else {
-            None
-        };
-
-        if let Some(op) = op {
-            let value = self.parse_assignment()?;
-            let span = self.current_span();
-            return Ok(Expr::CompoundAssign(CompoundAssignExpr {
-                op,
-                target: Box::new(expr),
-                value: Box::new(value),
-                span,
-            }));
-        }
-
-        Ok(expr)
-    }
-
-    /// Parse logical OR
-    fn parse_or(&mut self) -> ParseResult<Expr> {
-        let mut expr = self.parse_and()?;
-
-        while self.match_token(TokenKind::Or) {
-            let right = self.parse_and()?;
-            let span = self.current_span();
-            expr = Expr::Binary(BinaryExpr {
-                op: BinaryOp::Or,
-                left: Box::new(expr),
-                right: Box::new(right),
-                span,
-            });
-        }
-
-        Ok(expr)
-    }
-
-    /// Parse logical AND
-    fn parse_and(&mut self) -> ParseResult<Expr> {
-        let mut expr = self.parse_equality()?;
-
-        while self.match_token(TokenKind::And) {
-            let right = self.parse_equality()?;
-            let span = self.current_span();
-            expr = Expr::Binary(BinaryExpr {
-                op: BinaryOp::And,
-                left: Box::new(expr),
-                right: Box::new(right),
-                span,
-            });
-        }
-
-        Ok(expr)
-    }
-
-    /// Parse equality
-    fn parse_equality(&mut self) -> ParseResult<Expr> {
-        let mut expr = self.parse_comparison()?;
-
-        loop {
-            let op = if self.match_token(TokenKind::EqEq) {
-                BinaryOp::Eq
-            } else if self.match_token(TokenKind::NotEq) {
-                BinaryOp::NotEq
-            } else {
-                break;
-            };
-
-            let right = self.parse_comparison()?;
-            let span = self.current_span();
-            expr = Expr::Binary(BinaryExpr {
-                op,
-                left: Box::new(expr),
-                right: Box::new(right),
-                span,
-            });
-        }
-
-        Ok(expr)
+        Ok(Stmt::Expr(ExprStmt {
+            expr,
+            span: start_span,
+        }))
     }
 
-    /// Parse comparison
-    fn parse_comparison(&mut self) -> ParseResult<Expr> {
-        let mut expr = self.parse_term()?;
+    /// Parse expression (entry point for expression parsing)
+    fn parse_expr(&mut self) -> ParseResult<Expr> {
+        self.parse_assignment()
+    }
 
-        loop {
-            let op = if self.match_token(TokenKind::Lt) {
-                BinaryOp::Lt
-            } else if self.match_token(TokenKind::Gt) {
-                BinaryOp::Gt
-            } else if self.match_token(TokenKind::LtEq) {
-                BinaryOp::LtEq
-            } else if self.match_token(TokenKind::GtEq) {
-                BinaryOp::GtEq
-            } else {
-                break;
-            };
+    /// Parse assignment expression (right-associative; lowest precedence)
+    fn parse_assignment(&mut self) -> ParseResult<Expr> {
+        let expr = self.parse_range(Restrictions::NONE)?;
 
-            let right = self.parse_term()?;
+        if self.match_token(TokenKind::Eq) {
+            let value = self.parse_assignment()?;
             let span = self.current_span();
-            expr = Expr::Binary(BinaryExpr {
-                op,
-                left: Box::new(expr),
-                right: Box::new(right),
+            return Ok(Expr::Assign(AssignExpr {
+                target: Box::new(expr),
+                value: Box::new(value),
                 span,
-            });
+            }));
         }
 
-        Ok(expr)
-    }
-
-    /// Parse term (addition/subtraction)
-    fn parse_term(&mut self) -> ParseResult<Expr> {
-        let mut expr = self.parse_factor()?;
-
-        loop {
-            let op = if self.match_token(TokenKind::Plus) {
-                BinaryOp::Add
-            } else if self.match_token(TokenKind::Minus) {
-                BinaryOp::Sub
-            } else {
-                break;
-            };
+        // Compound assignment
+        let op = if self.match_token(TokenKind::PlusEq) {
+            Some(CompoundAssignOp::AddAssign)
+        } else if self.match_token(TokenKind::MinusEq) {
+            Some(CompoundAssignOp::SubAssign)
+        } else if self.match_token(TokenKind::StarEq) {
+            Some(CompoundAssignOp::MulAssign)
+        } else if self.match_token(TokenKind::SlashEq) {
+            Some(CompoundAssignOp::DivAssign)
+        } else {
+            None
+        };
 
-            let right = self.parse_factor()?;
+        if let Some(op) = op {
+            let value = self.parse_assignment()?;
             let span = self.current_span();
-            expr = Expr::Binary(BinaryExpr {
+            return Ok(Expr::CompoundAssign(CompoundAssignExpr {
                 op,
-                left: Box::new(expr),
-                right: Box::new(right),
+                target: Box::new(expr),
+                value: Box::new(value),
                 span,
-            });
+            }));
         }
 
         Ok(expr)
     }
 
-    /// Parse factor (multiplication/division)
-    fn parse_factor(&mut self) -> ParseResult<Expr> {
-        let mut expr = self.parse_unary()?;
+    /// Binding power (precedence) and associativity for each binary operator,
+    /// lowest-precedence first. A single declarative table replaces the old
+    /// `parse_or` -> `parse_and` -> ... -> `parse_factor` precedence cascade.
+    fn binary_op_bp(kind: &TokenKind) -> Option<(BinaryOp, u8, Fixity)> {
+        use TokenKind::*;
+        Some(match kind {
+            Or => (BinaryOp::Or, 1, Fixity::Left),
+            And => (BinaryOp::And, 2, Fixity::Left),
+            EqEq => (BinaryOp::Eq, 3, Fixity::Left),
+            NotEq => (BinaryOp::NotEq, 3, Fixity::Left),
+            Lt => (BinaryOp::Lt, 4, Fixity::Left),
+            Gt => (BinaryOp::Gt, 4, Fixity::Left),
+            LtEq => (BinaryOp::LtEq, 4, Fixity::Left),
+            GtEq => (BinaryOp::GtEq, 4, Fixity::Left),
+            Plus => (BinaryOp::Add, 5, Fixity::Left),
+            Minus => (BinaryOp::Sub, 5, Fixity::Left),
+            Star => (BinaryOp::Mul, 6, Fixity::Left),
+            Slash => (BinaryOp::Div, 6, Fixity::Left),
+            Percent => (BinaryOp::Mod, 6, Fixity::Left),
+            _ => return None,
+        })
+    }
+
+    /// Precedence-climbing (Pratt) parser for binary expressions. `restrictions`
+    /// is threaded down through every level instead of duplicating the whole
+    /// cascade as a separate `_no_struct` chain.
+    fn parse_expr_bp(&mut self, min_bp: u8, restrictions: Restrictions) -> ParseResult<Expr> {
+        let mut lhs = self.parse_unary_bp(restrictions)?;
 
         loop {
-            let op = if self.match_token(TokenKind::Star) {
-                BinaryOp::Mul
-            } else if self.match_token(TokenKind::Slash) {
-                BinaryOp::Div
-            } else if self.match_token(TokenKind::Percent) {
-                BinaryOp::Mod
-            } else {
-                break;
+            let kind = match self.peek() {
+                Some(tok) => tok.kind.clone(),
+                None => break,
+            };
+
+            let (op, bp, fixity) = match Self::binary_op_bp(&kind) {
+                Some(entry) => entry,
+                None => break,
             };
 
-            let right = self.parse_unary()?;
+            if bp < min_bp {
+                break;
+            }
+
+            self.advance();
+            let next_min_bp = match fixity {
+                Fixity::Left => bp + 1,
+                Fixity::Right => bp,
+            };
+            let rhs = self.parse_expr_bp(next_min_bp, restrictions)?;
             let span = self.current_span();
-            expr = Expr::Binary(BinaryExpr {
+            lhs = Expr::Binary(BinaryExpr {
                 op,
-                left: Box::new(expr),
-                right: Box::new(right),
+                left: Box::new(lhs),
+                right: Box::new(rhs),
                 span,
             });
         }
 
-        Ok(expr)
+        Ok(lhs)
     }
 
     /// Parse unary expression
-    fn parse_unary(&mut self) -> ParseResult<Expr> {
+    fn parse_unary_bp(&mut self, restrictions: Restrictions) -> ParseResult<Expr> {
         let span = self.current_span();
 
         if self.match_token(TokenKind::Not) {
-            let operand = self.parse_unary()?;
+            let operand = self.parse_unary_bp(restrictions)?;
             return Ok(Expr::Unary(UnaryExpr {
                 op: UnaryOp::Not,
                 operand: Box::new(operand),
@@ -1539,7 +2110,7 @@ impl Parser {
         }
 
         if self.match_token(TokenKind::Minus) {
-            let operand = self.parse_unary()?;
+            let operand = self.parse_unary_bp(restrictions)?;
             return Ok(Expr::Unary(UnaryExpr {
                 op: UnaryOp::Neg,
                 operand: Box::new(operand),
@@ -1548,7 +2119,7 @@ impl Parser {
         }
 
         if self.match_token(TokenKind::Star) {
-            let operand = self.parse_unary()?;
+            let operand = self.parse_unary_bp(restrictions)?;
             return Ok(Expr::Deref(DerefExpr {
                 expr: Box::new(operand),
                 span,
@@ -1557,7 +2128,7 @@ impl Parser {
 
         if self.match_token(TokenKind::Ampersand) {
             let mutable = self.match_token(TokenKind::Mut);
-            let operand = self.parse_unary()?;
+            let operand = self.parse_unary_bp(restrictions)?;
             return Ok(Expr::Ref(RefExpr {
                 mutable,
                 expr: Box::new(operand),
@@ -1565,18 +2136,18 @@ impl Parser {
             }));
         }
 
-        self.parse_call()
+        self.parse_call_bp(restrictions)
     }
 
     /// Parse call/member/index expression
-    fn parse_call(&mut self) -> ParseResult<Expr> {
-        let mut expr = self.parse_primary()?;
+    fn parse_call_bp(&mut self, restrictions: Restrictions) -> ParseResult<Expr> {
+        let mut expr = self.parse_primary(restrictions)?;
 
         loop {
             // Skip newlines to allow method chaining across lines
             self.skip_newlines();
 
-            if self.match_token(TokenKind::LParen) {
+            if !restrictions.contains(Restrictions::NO_CALL) && self.match_token(TokenKind::LParen) {
                 // Function call
                 let args = self.parse_args()?;
                 self.expect(TokenKind::RParen)?;
@@ -1598,6 +2169,7 @@ impl Parser {
                     optional: false,
                     computed: false,
                     is_path: false,
+                    type_args: Vec::new(),
                     span,
                 });
             } else if self.match_token(TokenKind::QuestionDot) {
@@ -1610,6 +2182,7 @@ impl Parser {
                     optional: true,
                     computed: false,
                     is_path: false,
+                    type_args: Vec::new(),
                     span,
                 });
             } else if self.match_token(TokenKind::LBracket) {
@@ -1622,6 +2195,45 @@ impl Parser {
                     index: Box::new(index),
                     span,
                 });
+            } else if self.check(TokenKind::ColonColon) && matches!(self.peek_at(1).map(|t| &t.kind), Some(TokenKind::Lt)) {
+                // Turbofish: `::<T, U, ...>`, only recognized right after `::`
+                // so a bare `<` elsewhere still parses as the less-than operator.
+                self.advance(); // ::
+                let type_args = self.parse_turbofish_type_args()?;
+
+                if !restrictions.contains(Restrictions::NO_CALL) && self.match_token(TokenKind::LParen) {
+                    // `parse::<u32>(...)` - the turbofish belongs to this call directly.
+                    let args = self.parse_args()?;
+                    self.expect(TokenKind::RParen)?;
+                    let span = self.current_span();
+                    expr = Expr::Call(CallExpr {
+                        callee: Box::new(expr),
+                        args,
+                        type_args,
+                        optional: false,
+                        span,
+                    });
+                } else {
+                    // `HashMap::<String, i32>::new(...)` - the turbofish belongs to
+                    // the path segment it's attached to, so fold it into the next
+                    // `::segment` of the path.
+                    self.expect(TokenKind::ColonColon)?;
+                    let method = if let Some(ast_type) = self.try_expect_ast_type() {
+                        ast_type
+                    } else {
+                        self.expect_ident()?
+                    };
+                    let span = self.current_span();
+                    expr = Expr::Member(MemberExpr {
+                        object: Box::new(expr),
+                        property: method,
+                        optional: false,
+                        computed: false,
+                        is_path: true,
+                        type_args,
+                        span,
+                    });
+                }
             } else if self.match_token(TokenKind::ColonColon) {
                 // Static method call like HashMap::new or Expr::CallExpression
                 let method = if let Some(ast_type) = self.try_expect_ast_type() {
@@ -1636,12 +2248,17 @@ impl Parser {
                     optional: false,
                     computed: false,
                     is_path: true,
+                    type_args: Vec::new(),
                     span,
                 });
             } else if self.match_token(TokenKind::Question) {
                 // Try operator: expr?
                 let span = self.current_span();
                 expr = Expr::Try(Box::new(expr));
+            } else if self.check(TokenKind::Lt) && self.looks_like_missing_turbofish() {
+                return Err(self.error(
+                    "generic arguments must be written as `::<T>` here (missing `::` before `<`)",
+                ));
             } else {
                 break;
             }
@@ -1650,6 +2267,60 @@ impl Parser {
         Ok(expr)
     }
 
+    /// Parse the comma-separated type list inside a turbofish `::<...>`,
+    /// including the empty-list edge case `::<>`.
+    fn parse_turbofish_type_args(&mut self) -> ParseResult<Vec<Type>> {
+        self.expect(TokenKind::Lt)?;
+        let mut type_args = Vec::new();
+        if !self.check(TokenKind::Gt) {
+            type_args.push(self.parse_type()?);
+            while self.match_token(TokenKind::Comma) {
+                if self.check(TokenKind::Gt) {
+                    break;
+                }
+                type_args.push(self.parse_type()?);
+            }
+        }
+        self.expect(TokenKind::Gt)?;
+        Ok(type_args)
+    }
+
+    /// Heuristic lookahead used only to produce a friendlier error: does the
+    /// `<` at the current position look like a forgotten-`::` turbofish
+    /// (`<...>` followed by a call or another path segment) rather than the
+    /// less-than operator? Scans forward over a balanced `<...>` group
+    /// without consuming any tokens.
+    fn looks_like_missing_turbofish(&self) -> bool {
+        let mut depth: i32 = 0;
+        let mut offset = 0;
+        loop {
+            let kind = match self.peek_at(offset) {
+                Some(token) => &token.kind,
+                None => return false,
+            };
+            match kind {
+                TokenKind::Lt => depth += 1,
+                TokenKind::Gt => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return matches!(
+                            self.peek_at(offset + 1).map(|t| &t.kind),
+                            Some(TokenKind::LParen) | Some(TokenKind::ColonColon)
+                        );
+                    }
+                }
+                TokenKind::Semicolon | TokenKind::LBrace | TokenKind::RBrace | TokenKind::Newline => {
+                    return false;
+                }
+                _ => {}
+            }
+            offset += 1;
+            if offset > 64 {
+                return false;
+            }
+        }
+    }
+
     /// Parse function call arguments
     fn parse_args(&mut self) -> ParseResult<Vec<Expr>> {
         let mut args = Vec::new();
@@ -1661,7 +2332,8 @@ impl Parser {
 
         loop {
             self.skip_newlines();
-            args.push(self.parse_expr()?);
+            let arg = self.parse_expr();
+            args.push(self.recover_expr(arg)?);
             self.skip_newlines();
             if !self.match_token(TokenKind::Comma) {
                 break;
@@ -1671,9 +2343,119 @@ impl Parser {
         Ok(args)
     }
 
+    /// If the parser is positioned at `name!`, consume the name and the `!`
+    /// and return the macro's name; otherwise leave the cursor untouched.
+    fn try_macro_name(&mut self) -> Option<String> {
+        let name = match self.peek() {
+            Some(Token { kind: TokenKind::Ident(name), .. }) => name.clone(),
+            Some(Token { kind: TokenKind::Matches, .. }) => "matches".to_string(),
+            _ => return None,
+        };
+        if !matches!(self.peek_at(1).map(|t| &t.kind), Some(TokenKind::Not)) {
+            return None;
+        }
+        self.advance(); // name
+        self.advance(); // !
+        Some(name)
+    }
+
+    /// Parse a macro invocation's delimiter group: `(...)`, `[...]`, or
+    /// `{...}` - the delimiter is tracked on the resulting node, mirroring
+    /// rustc's `MacDelimiter`, so codegen can tell `vec![...]` apart from
+    /// `vec!(...)`. Arguments inside `(`/`[` are a comma-separated expression
+    /// list (reusing the same grammar as `parse_args`); `{...}` keeps the raw
+    /// token span instead, since a brace-bodied macro body isn't necessarily a
+    /// comma-separated expression list.
+    fn parse_macro_call(&mut self, name: String, span: Span) -> ParseResult<Expr> {
+        if self.check(TokenKind::LBrace) {
+            let tokens = self.parse_brace_token_tree()?;
+            return Ok(Expr::MacroCall(MacroCallExpr {
+                name,
+                delimiter: MacroDelimiter::Brace,
+                args: Vec::new(),
+                tokens,
+                span,
+            }));
+        }
+
+        let (open, close, delimiter) = if self.check(TokenKind::LBracket) {
+            (TokenKind::LBracket, TokenKind::RBracket, MacroDelimiter::Bracket)
+        } else {
+            (TokenKind::LParen, TokenKind::RParen, MacroDelimiter::Paren)
+        };
+
+        self.expect(open)?;
+        self.skip_newlines();
+        let mut args = Vec::new();
+        if !self.check(close.clone()) {
+            loop {
+                self.skip_newlines();
+                args.push(self.parse_expr()?);
+                self.skip_newlines();
+                if !self.match_token(TokenKind::Comma) {
+                    break;
+                }
+                self.skip_newlines();
+                if self.check(close.clone()) {
+                    break;
+                }
+            }
+        }
+        self.expect(close)?;
+
+        // `vec![...]` keeps producing the dedicated node the semantic layer
+        // already understands; every other macro (`format!`, `matches!`,
+        // `println!`, user macros, ...) produces a generic macro-call node.
+        if name == "vec" && delimiter == MacroDelimiter::Bracket {
+            return Ok(Expr::VecInit(VecInitExpr { elements: args, span }));
+        }
+
+        Ok(Expr::MacroCall(MacroCallExpr {
+            name,
+            delimiter,
+            args,
+            tokens: Vec::new(),
+            span,
+        }))
+    }
+
+    /// Capture the contents of a balanced `{ ... }` group verbatim, without
+    /// assigning them expression structure - used for brace-delimited macro
+    /// invocations.
+    fn parse_brace_token_tree(&mut self) -> ParseResult<Vec<Token>> {
+        self.expect(TokenKind::LBrace)?;
+        let mut tokens = Vec::new();
+        let mut depth = 1;
+
+        loop {
+            let tok = self
+                .peek()
+                .cloned()
+                .ok_or_else(|| self.error("Unterminated macro body"))?;
+
+            match tok.kind {
+                TokenKind::LBrace => depth += 1,
+                TokenKind::RBrace => {
+                    depth -= 1;
+                    if depth == 0 {
+                        self.advance();
+                        break;
+                    }
+                }
+                _ => {}
+            }
+
+            tokens.push(tok);
+            self.advance();
+        }
+
+        Ok(tokens)
+    }
+
     /// Parse primary expression
-    fn parse_primary(&mut self) -> ParseResult<Expr> {
+    fn parse_primary(&mut self, restrictions: Restrictions) -> ParseResult<Expr> {
         let span = self.current_span();
+        let allow_struct_literal = !restrictions.contains(Restrictions::NO_STRUCT_LITERAL);
 
         // Block expression
         if self.check(TokenKind::LBrace) {
@@ -1689,96 +2471,77 @@ impl Parser {
                 return Ok(Expr::Literal(Literal::Unit));
             }
             // Check for closure: |params| expr
-            if self.check(TokenKind::Pipe) {
+            if self.is_closure_start() {
                 return self.parse_closure(span);
             }
             let expr = self.parse_expr()?;
+
+            // A top-level comma makes this a tuple literal rather than a
+            // parenthesized expression - `(a,)` is the one-element tuple,
+            // `(a)` (no comma) stays `Expr::Paren`.
+            if self.check(TokenKind::Comma) {
+                let mut elements = vec![expr];
+                while self.match_token(TokenKind::Comma) {
+                    self.skip_newlines();
+                    if self.check(TokenKind::RParen) {
+                        break;
+                    }
+                    elements.push(self.parse_expr()?);
+                }
+                self.expect(TokenKind::RParen)?;
+                return Ok(Expr::Tuple(TupleExpr { elements, span }));
+            }
+
             self.expect(TokenKind::RParen)?;
             return Ok(Expr::Paren(Box::new(expr)));
         }
 
-        // Closure
-        if self.check(TokenKind::Pipe) {
-            return self.parse_closure(span);
-        }
+        // Array literal `[a, b, c]` or repeat form `[value; count]`
+        if self.match_token(TokenKind::LBracket) {
+            if self.check(TokenKind::RBracket) {
+                self.advance();
+                return Ok(Expr::Array(ArrayExpr { elements: Vec::new(), span }));
+            }
 
-        // Literal
-        if let Some(lit) = self.try_parse_literal() {
-            return Ok(Expr::Literal(lit));
-        }
+            let first = self.parse_expr()?;
 
-        // Vec initialization: vec![...]
-        if self.check_ident("vec") {
-            self.advance();
-            if self.match_token(TokenKind::Not) {
-                self.expect(TokenKind::LBracket)?;
-                self.skip_newlines();
-                let mut elements = Vec::new();
-                if !self.check(TokenKind::RBracket) {
-                    loop {
-                        elements.push(self.parse_expr()?);
-                        self.skip_newlines();
-                        if !self.match_token(TokenKind::Comma) {
-                            break;
-                        }
-                        self.skip_newlines();
-                        // Allow trailing comma
-                        if self.check(TokenKind::RBracket) {
-                            break;
-                        }
-                    }
-                }
+            if self.match_token(TokenKind::Semicolon) {
+                let count = self.parse_expr()?;
                 self.expect(TokenKind::RBracket)?;
-                return Ok(Expr::VecInit(VecInitExpr { elements, span }));
-            } else {
-                // Just identifier "vec"
-                return Ok(Expr::Ident(IdentExpr {
-                    name: "vec".to_string(),
+                return Ok(Expr::ArrayRepeat(ArrayRepeatExpr {
+                    value: Box::new(first),
+                    count: Box::new(count),
                     span,
                 }));
             }
-        }
 
-        // format! macro (treat as function call)
-        if self.check_ident("format") {
-            self.advance();
-            if self.match_token(TokenKind::Not) {
-                self.expect(TokenKind::LParen)?;
-                let args = self.parse_args()?;
-                self.expect(TokenKind::RParen)?;
-                return Ok(Expr::Call(CallExpr {
-                    callee: Box::new(Expr::Ident(IdentExpr {
-                        name: "format".to_string(),
-                        span,
-                    })),
-                    args,
-                    type_args: Vec::new(),
-                    optional: false,
-                    span,
-                }));
-            } else {
-                return Ok(Expr::Ident(IdentExpr {
-                    name: "format".to_string(),
-                    span,
-                }));
+            let mut elements = vec![first];
+            while self.match_token(TokenKind::Comma) {
+                self.skip_newlines();
+                if self.check(TokenKind::RBracket) {
+                    break;
+                }
+                elements.push(self.parse_expr()?);
             }
+            self.expect(TokenKind::RBracket)?;
+            return Ok(Expr::Array(ArrayExpr { elements, span }));
         }
 
-        // matches! macro
-        if self.match_token(TokenKind::Matches) {
-            self.expect(TokenKind::LParen)?;
-            let args = self.parse_args()?;
-            self.expect(TokenKind::RParen)?;
-            return Ok(Expr::Call(CallExpr {
-                callee: Box::new(Expr::Ident(IdentExpr {
-                    name: "matches!".to_string(),
-                    span,
-                })),
-                args,
-                type_args: Vec::new(),
-                optional: false,
-                span,
-            }));
+        // Closure: `|params| body`, `move |params| body`, or the empty-parameter `|| body`
+        if self.is_closure_start() {
+            return self.parse_closure(span);
+        }
+
+        // Literal
+        if let Some(lit) = self.try_parse_literal() {
+            return Ok(Expr::Literal(lit));
+        }
+
+        // Macro invocation: `ident!(...)`, `ident![...]`, or `ident!{...}` -
+        // covers `vec!`, `format!`, `matches!`, and any user-written macro
+        // alike instead of special-casing each name.
+        if let Some(name) = self.try_macro_name() {
+            return self.parse_macro_call(name, span);
         }
 
         // Self
@@ -1791,7 +2554,7 @@ impl Parser {
 
         if self.match_token(TokenKind::SelfType) {
             let name = "Self".to_string();
-            if self.check(TokenKind::LBrace) {
+            if allow_struct_literal && self.check(TokenKind::LBrace) {
                 return self.parse_struct_init(name, span);
             }
             return Ok(Expr::Ident(IdentExpr {
@@ -1803,7 +2566,7 @@ impl Parser {
         // Identifier or struct init
         if let Some(name) = self.try_expect_ident() {
             // Check for struct initialization or wildcard pattern TypeName(_)
-            if self.check(TokenKind::LBrace) {
+            if allow_struct_literal && self.check(TokenKind::LBrace) {
                 return self.parse_struct_init(name, span);
             }
             // Check for wildcard pattern: TypeName(_)
@@ -1820,6 +2583,7 @@ impl Parser {
                             name: "_".to_string(),
                             span,
                         }))],
+                        rest: None,
                         span,
                     }));
                 }
@@ -1843,7 +2607,7 @@ impl Parser {
         // AST node type as identifier
         if let Some(name) = self.try_expect_ast_type() {
             // Check for struct initialization
-            if self.check(TokenKind::LBrace) {
+            if allow_struct_literal && self.check(TokenKind::LBrace) {
                 return self.parse_struct_init(name, span);
             }
             // Check for wildcard pattern: TypeName(_)
@@ -1860,6 +2624,7 @@ impl Parser {
                             name: "_".to_string(),
                             span,
                         }))],
+                        rest: None,
                         span,
                     }));
                 }
@@ -1876,7 +2641,7 @@ impl Parser {
         // Self type (can be used for struct initialization)
         if self.match_token(TokenKind::SelfType) {
             let name = "Self".to_string();
-            if self.check(TokenKind::LBrace) {
+            if allow_struct_literal && self.check(TokenKind::LBrace) {
                 return self.parse_struct_init(name, span);
             }
             return Ok(Expr::Ident(IdentExpr { name, span }));
@@ -1896,28 +2661,68 @@ impl Parser {
         if let Some(name) = type_name {
             self.advance();
             let name = name.to_string();
-            if self.check(TokenKind::LBrace) {
+            if allow_struct_literal && self.check(TokenKind::LBrace) {
                 return self.parse_struct_init(name, span);
             }
             return Ok(Expr::Ident(IdentExpr { name, span }));
         }
 
-        Err(self.error("Expected expression"))
+        let err = Err(self.error("Expected expression"));
+        self.recover_expr(err)
+    }
+
+    /// Whether the parser is positioned at the start of a closure: an optional
+    /// `move` keyword followed by `|` (parameter list) or `||` (no parameters).
+    fn is_closure_start(&self) -> bool {
+        if self.check(TokenKind::Move) {
+            return true;
+        }
+        self.check(TokenKind::Pipe) || self.check(TokenKind::OrOr)
     }
 
-    /// Parse closure expression
+    /// Parse closure expression: `|x, y: i32| -> i32 { ... }`, `move |x| x + 1`,
+    /// or the empty-parameter `|| body` (also accepted as the single `||` token).
     fn parse_closure(&mut self, span: Span) -> ParseResult<Expr> {
-        self.expect(TokenKind::Pipe)?;
-        let mut params = Vec::new();
-        if !self.check(TokenKind::Pipe) {
-            loop {
-                params.push(self.expect_ident()?);
-                if !self.match_token(TokenKind::Comma) {
-                    break;
+        let capture = if self.match_token(TokenKind::Move) {
+            CaptureMode::Move
+        } else {
+            CaptureMode::Ref
+        };
+
+        let params = if self.match_token(TokenKind::OrOr) {
+            // `||` - no parameters, already consumed as a single token
+            Vec::new()
+        } else {
+            self.expect(TokenKind::Pipe)?;
+            let mut params = Vec::new();
+            if !self.check(TokenKind::Pipe) {
+                loop {
+                    let param_span = self.current_span();
+                    let name = self.expect_ident()?;
+                    let ty = if self.match_token(TokenKind::Colon) {
+                        self.parse_type()?
+                    } else {
+                        Type::Inferred
+                    };
+                    params.push(Param {
+                        name,
+                        ty,
+                        span: param_span,
+                    });
+                    if !self.match_token(TokenKind::Comma) {
+                        break;
+                    }
                 }
             }
-        }
-        self.expect(TokenKind::Pipe)?;
+            self.expect(TokenKind::Pipe)?;
+            params
+        };
+
+        let return_type = if self.match_token(TokenKind::Arrow) {
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
 
         // Closure body can be either an expression or a block
         let body = if self.check(TokenKind::LBrace) {
@@ -1928,7 +2733,9 @@ impl Parser {
         };
 
         Ok(Expr::Closure(ClosureExpr {
+            capture,
             params,
+            return_type,
             body: Box::new(body),
             span,
         }))
@@ -1938,6 +2745,7 @@ impl Parser {
     fn parse_struct_init(&mut self, name: String, span: Span) -> ParseResult<Expr> {
         self.expect(TokenKind::LBrace)?;
         let mut fields = Vec::new();
+        let mut rest = None;
 
         loop {
             self.skip_newlines();
@@ -1945,10 +2753,26 @@ impl Parser {
                 break;
             }
 
+            // Functional-update rest clause: `..base`. Only valid as the
+            // final entry, so a field seen after it is a clear error rather
+            // than silently discarded.
+            if self.match_token(TokenKind::DotDot) {
+                let value = self.parse_expr();
+                rest = Some(Box::new(self.recover_expr(value)?));
+                self.skip_newlines();
+                if self.match_token(TokenKind::Comma) {
+                    self.skip_newlines();
+                    if !self.check(TokenKind::RBrace) {
+                        return Err(self.error("`..` struct-update base must be the last entry in a struct literal"));
+                    }
+                }
+                break;
+            }
+
             let field_name = self.expect_ident()?;
             self.expect(TokenKind::Colon)?;
-            let value = self.parse_expr()?;
-            fields.push((field_name, value));
+            let value = self.parse_expr();
+            fields.push((field_name, self.recover_expr(value)?));
 
             self.skip_newlines();
             if !self.match_token(TokenKind::Comma) {
@@ -1957,7 +2781,7 @@ impl Parser {
         }
 
         self.expect(TokenKind::RBrace)?;
-        Ok(Expr::StructInit(StructInitExpr { name, fields, span }))
+        Ok(Expr::StructInit(StructInitExpr { name, fields, rest, span }))
     }
 
     /// Try to parse a literal
@@ -2000,6 +2824,11 @@ impl Parser {
         self.tokens.get(self.pos)
     }
 
+    /// Peek `offset` tokens ahead of the current position without consuming.
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset)
+    }
+
     fn advance(&mut self) -> Option<&Token> {
         if !self.is_at_end() {
             self.pos += 1;
@@ -2037,6 +2866,25 @@ impl Parser {
         }
     }
 
+    /// Expect the `;` that terminates a statement. A `}` immediately where the
+    /// `;` should be almost always means the semicolon was just forgotten, so
+    /// report that specifically - with a fix-it suggestion to insert it - rather
+    /// than the generic "Expected Semicolon".
+    fn expect_semi(&mut self) -> ParseResult<()> {
+        if self.match_token(TokenKind::Semicolon) {
+            return Ok(());
+        }
+        if self.check(TokenKind::RBrace) {
+            let span = self.current_span();
+            return Err(ParseError::new("missing `;` before `}`", span).with_suggestion(
+                ";",
+                span,
+                Applicability::MachineApplicable,
+            ));
+        }
+        self.expect(TokenKind::Semicolon)
+    }
+
     fn expect_ident(&mut self) -> ParseResult<String> {
         match self.peek() {
             Some(Token { kind: TokenKind::Ident(name), .. }) => {
@@ -1,14 +1,66 @@
 //! Name resolution pass for RustScript
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 use crate::parser::*;
 use crate::semantic::{SemanticError, TypeEnv, TypeInfo, types::ast_type_to_type_info};
 use crate::mapping::get_node_mapping;
 
+/// Loads and caches the exports of `./`/`../` file-module imports, so a
+/// diamond import graph (A and B both import C) parses C once, and an
+/// import cycle (A imports B imports A) is reported instead of recursed
+/// into forever. Analogous to rustc's `CrateLoader`, scoped down to this
+/// crate's single-file-at-a-time parser/resolver pipeline.
+struct ModuleLoader {
+    base_dir: PathBuf,
+    cache: HashMap<PathBuf, HashMap<String, TypeInfo>>,
+    in_progress: std::collections::HashSet<PathBuf>,
+}
+
 /// Name resolver - resolves all identifiers and builds the type environment
 pub struct Resolver {
     env: TypeEnv,
     errors: Vec<SemanticError>,
+    /// File-module loading is opt-in: resolving a `./`/`../` import needs a
+    /// directory to resolve the relative path against, which nothing
+    /// passes into `new()`/`resolve()` today. Left `None`, file imports
+    /// fall back to their old `Unknown`-typed behavior; a caller that
+    /// knows the source file's directory can opt in via `set_base_dir`.
+    loader: Option<ModuleLoader>,
+    /// Names brought in by a specific `use ./path::{Name}` import, whose
+    /// shape (struct, enum, function, ...) isn't known at import time - see
+    /// `resolve_use`. Kept separate from `env`'s own struct/enum tables so
+    /// a type-position check (`Expr::StructInit`, `resolve_type_ref`) can
+    /// tell "unknown because imported" from "unknown because a same-named
+    /// local variable happens to exist" - those two checks always query
+    /// `get_struct_fields`/`imported_names`, never `env.lookup`'s flat value
+    /// namespace, specifically so a local variable can't shadow a struct
+    /// name at either site. That's every type-position check this resolver
+    /// has, not a full value/type `PerNS` split on `TypeEnv` itself (which
+    /// would also need an enum-lookup getter - `TypeEnv` only exposes
+    /// `get_struct_fields` - and would let `env.lookup` itself stop
+    /// conflating the namespaces instead of working around it per call
+    /// site).
+    imported_names: std::collections::HashSet<String>,
+    /// Every name ever passed to `define_value`/`declare_struct`/
+    /// `declare_enum`/`declare_function`, flattened across all scopes.
+    /// `TypeEnv` has no API to enumerate what it currently has in scope, so
+    /// this is the candidate pool `suggest` searches for a "did you mean"
+    /// hint - an over-approximation (it doesn't forget names when a scope
+    /// pops) rather than an exact in-scope set, but close enough for a
+    /// best-effort suggestion.
+    declared_names: std::collections::HashSet<String>,
+    /// `(span, suggested name)` for every RS006/RS007 site where a
+    /// plausible near-miss was found, in discovery order.
+    suggestions: Vec<(Span, String)>,
+    /// Names of the loops currently being resolved, innermost last (`None`
+    /// for an unlabeled loop) - rustc-style rib stack for `break`/`continue`
+    /// labels. `Parser::loop_stack` already rejects an unknown label or a
+    /// break/continue outside any loop before a `Program` ever reaches
+    /// here, so this can never actually fire on a program that parsed
+    /// successfully; it exists so the resolver doesn't silently depend on
+    /// that parse-time guarantee to stay correct.
+    loop_labels: Vec<Option<String>>,
 }
 
 impl Resolver {
@@ -16,6 +68,135 @@ impl Resolver {
         Self {
             env: TypeEnv::new(),
             errors: Vec::new(),
+            imported_names: std::collections::HashSet::new(),
+            declared_names: std::collections::HashSet::new(),
+            suggestions: Vec::new(),
+            loop_labels: Vec::new(),
+            loader: None,
+        }
+    }
+
+    /// Opt into loading `./`/`../` file-module imports from disk, resolving
+    /// their relative paths against `base_dir` (typically the importing
+    /// file's own directory).
+    pub fn set_base_dir(&mut self, base_dir: impl Into<PathBuf>) {
+        self.loader = Some(ModuleLoader {
+            base_dir: base_dir.into(),
+            cache: HashMap::new(),
+            in_progress: std::collections::HashSet::new(),
+        });
+    }
+
+    /// Define `name` in the value namespace, also recording it as a
+    /// candidate for future "did you mean" suggestions.
+    fn define_value(&mut self, name: String, ty: TypeInfo) {
+        self.declared_names.insert(name.clone());
+        self.env.define(name, ty);
+    }
+
+    /// Every RS006/RS007 site paired with the closest in-scope name found
+    /// for it, for a caller that wants to render "help: did you mean `x`?"
+    /// alongside the underlying `SemanticError`.
+    pub fn suggestions(&self) -> &[(Span, String)] {
+        &self.suggestions
+    }
+
+    /// The closest previously-declared name to `name` by Levenshtein
+    /// distance, or `None` if nothing is close enough to be worth
+    /// suggesting. Candidates whose first character differs are skipped
+    /// before the (more expensive) distance computation runs, and the best
+    /// match is only returned if its distance is at most
+    /// `max(1, name.len() / 3)` - close enough to plausibly be a typo,
+    /// rather than an unrelated name that happens to be shortest edit
+    /// distance away.
+    fn suggest(&self, name: &str) -> Option<String> {
+        let first_char = name.chars().next()?;
+        let threshold = (name.chars().count() / 3).max(1);
+
+        let mut best: Option<(usize, &String)> = None;
+        for candidate in &self.declared_names {
+            if candidate == name {
+                continue;
+            }
+            if candidate.chars().next() != Some(first_char) {
+                continue;
+            }
+            let distance = levenshtein(name, candidate);
+            let is_better = match &best {
+                Some((best_distance, _)) => distance < *best_distance,
+                None => true,
+            };
+            if is_better {
+                best = Some((distance, candidate));
+            }
+        }
+
+        best.filter(|(distance, _)| *distance <= threshold)
+            .map(|(_, candidate)| candidate.clone())
+    }
+
+    /// Validates a named type reference (`let x: Foo = ...`, a parameter
+    /// type, ...) against the type namespace the same way `Expr::StructInit`
+    /// does - `get_struct_fields`, or an import whose shape isn't known yet
+    /// - rather than `env.lookup`'s value namespace, so a local variable
+    /// that happens to share a struct's name can't mask a typo'd one here
+    /// either. Enums have no equivalent getter (see `load_file_module`'s
+    /// doc comment) so they're not checked; `Primitive`/`Unit`/`Inferred`
+    /// need no check, and `Container`/`Reference`/`Tuple` just recurse into
+    /// their element types.
+    fn resolve_type_ref(&mut self, ty: &Type, span: Span) {
+        match ty {
+            Type::Named(name) if name != "Self" => {
+                if self.env.get_struct_fields(name).is_none() && !self.imported_names.contains(name) {
+                    let suggestion = self.suggest(name);
+                    let message = match &suggestion {
+                        Some(candidate) => format!(
+                            "Unknown type: {} (help: did you mean `{}`?)",
+                            name, candidate
+                        ),
+                        None => format!("Unknown type: {}", name),
+                    };
+                    self.errors.push(SemanticError::new("RS007", message, span));
+                    if let Some(candidate) = suggestion {
+                        self.suggestions.push((span, candidate));
+                    }
+                }
+            }
+            Type::Named(_) | Type::Primitive(_) | Type::Unit | Type::Inferred => {}
+            Type::Container { type_args, .. } => {
+                for arg in type_args {
+                    self.resolve_type_ref(arg, span);
+                }
+            }
+            Type::Reference { inner, .. } => self.resolve_type_ref(inner, span),
+            Type::Tuple(types) => {
+                for t in types {
+                    self.resolve_type_ref(t, span);
+                }
+            }
+        }
+    }
+
+    /// Validate a `break`/`continue`'s optional label against `loop_labels`:
+    /// a labeled one must name a loop currently being resolved, and an
+    /// unlabeled one needs some enclosing loop at all. Mirrors
+    /// `Parser::lookup_loop`'s check at parse time - see `loop_labels`'s
+    /// doc comment for why this is unreachable on a program that parsed.
+    fn check_loop_label(&mut self, label: &Option<Label>, span: Span) {
+        let found = match label {
+            Some(label) => self
+                .loop_labels
+                .iter()
+                .rev()
+                .any(|name| name.as_deref() == Some(label.name.as_str())),
+            None => !self.loop_labels.is_empty(),
+        };
+        if !found {
+            let message = match label {
+                Some(label) => format!("use of undeclared label '{}", label.name),
+                None => "cannot break/continue outside of a loop".to_string(),
+            };
+            self.errors.push(SemanticError::new("RS020", message, span));
         }
     }
 
@@ -53,7 +234,7 @@ impl Resolver {
             // Register the module name (or alias if provided)
             if use_stmt.alias.is_some() || use_stmt.imports.is_empty() {
                 let module_name = use_stmt.alias.clone().unwrap_or_else(|| use_stmt.path.clone());
-                self.env.define(
+                self.define_value(
                     module_name.clone(),
                     TypeInfo::Module {
                         name: module_name,
@@ -61,13 +242,40 @@ impl Resolver {
                 );
             }
 
-            // Register specific imports as unknown types (we don't know what they are yet)
-            // They could be functions, structs, or other values
+            // Built-in modules have a known export table, so a specific
+            // import binds its real signature and a typo is caught here
+            // instead of surfacing as a mystery `RS006` where it's used.
+            // A file module's exports come from actually loading it, via
+            // `load_file_module` below, only when a base directory has
+            // been configured with `set_base_dir` - without one there's no
+            // path to resolve the import against, so it falls back to the
+            // old `Unknown` behavior.
+            let exports = if is_file_module {
+                self.load_file_module(&use_stmt.path, use_stmt.span)
+            } else {
+                module_exports(&use_stmt.path)
+            };
             for import_name in &use_stmt.imports {
-                self.env.define(
-                    import_name.clone(),
-                    TypeInfo::Unknown,  // We don't know the type yet
-                );
+                match exports.as_ref().and_then(|e| e.get(import_name)) {
+                    Some(ty) => {
+                        self.define_value(import_name.clone(), ty.clone());
+                        self.imported_names.insert(import_name.clone());
+                    }
+                    None if exports.is_some() => {
+                        self.errors.push(SemanticError::new(
+                            "RS007",
+                            format!("Module '{}' has no member '{}'", use_stmt.path, import_name),
+                            use_stmt.span,
+                        ));
+                    }
+                    None => {
+                        // No export table available (built-in lookup
+                        // failed, or no base directory configured to load
+                        // a file module) - shape unknown.
+                        self.define_value(import_name.clone(), TypeInfo::Unknown);
+                        self.imported_names.insert(import_name.clone());
+                    }
+                }
             }
         } else {
             self.errors.push(SemanticError::new(
@@ -78,6 +286,126 @@ impl Resolver {
         }
     }
 
+    /// Loads `rel_path` (resolved against the configured base directory),
+    /// parses it, and runs a reduced resolution pass - the file's own
+    /// `use`s (so an import chain back to a file already `in_progress` is
+    /// detected, not just a single hop), then the struct/enum/function
+    /// declaration passes, no body checking - to collect its top-level
+    /// exports. The nested resolver shares this loader (not a fresh one)
+    /// for exactly that reason: a fresh `in_progress` set per file could
+    /// never see a cycle more than one import deep. Returns `None` (rather
+    /// than an empty table) when no base directory is configured, a cycle
+    /// is detected, or the file can't be read/parsed, each of which is
+    /// reported via `self.errors` pointing at the importing `use`
+    /// statement's span; callers distinguish "no table" from "table, but
+    /// this name isn't in it" the same way `module_exports` lets them for
+    /// built-ins.
+    ///
+    /// Enum exports aren't re-bound here - `TypeEnv` exposes
+    /// `get_struct_fields` for structs but no equivalent getter for
+    /// `define_enum`'s table, so an enum declared in an imported file is
+    /// visible to `use`'s existence check but not re-typed for the
+    /// importer. A real fix needs that getter added where `TypeEnv` itself
+    /// lives, in `crate::semantic::types`.
+    fn load_file_module(&mut self, rel_path: &str, use_span: Span) -> Option<HashMap<String, TypeInfo>> {
+        let base_dir = self.loader.as_ref()?.base_dir.clone();
+        let full_path = base_dir.join(rel_path);
+
+        if let Some(cached) = self.loader.as_ref().and_then(|l| l.cache.get(&full_path)) {
+            return Some(cached.clone());
+        }
+        if self.loader.as_ref().map(|l| l.in_progress.contains(&full_path)).unwrap_or(false) {
+            self.errors.push(SemanticError::new(
+                "RS017",
+                format!("Import cycle detected while loading '{}'", rel_path),
+                use_span,
+            ));
+            return None;
+        }
+
+        let source = match std::fs::read_to_string(&full_path) {
+            Ok(source) => source,
+            Err(err) => {
+                self.errors.push(SemanticError::new(
+                    "RS017",
+                    format!("Could not read module '{}': {}", rel_path, err),
+                    use_span,
+                ));
+                return None;
+            }
+        };
+
+        if let Some(loader) = self.loader.as_mut() {
+            loader.in_progress.insert(full_path.clone());
+        }
+
+        let mut lexer = crate::Lexer::new(&source);
+        let tokens = lexer.tokenize();
+        let mut parser = crate::Parser::new(tokens);
+        let exports = match parser.parse() {
+            Ok(program) => {
+                let mut nested = Resolver::new();
+                // Share this loader - not a fresh one - so a path back to a
+                // file already in `in_progress` (A imports B imports A) is
+                // actually detected instead of starting a new, empty
+                // `in_progress` set that can never see it. This is the only
+                // place `nested`'s own `use`s get resolved at all; without
+                // it a cycle can never be reached in the first place.
+                nested.loader = self.loader.take();
+                for use_stmt in &program.uses {
+                    nested.resolve_use(use_stmt);
+                }
+                self.loader = nested.loader.take();
+                self.errors.append(&mut nested.errors);
+
+                let items = top_level_items(&program.decl);
+                for item in items {
+                    match item {
+                        PluginItem::Struct(s) => nested.declare_struct(s),
+                        PluginItem::Enum(e) => nested.declare_enum(e),
+                        _ => {}
+                    }
+                }
+                for item in items {
+                    if let PluginItem::Function(f) = item {
+                        nested.declare_function(f);
+                    }
+                }
+
+                let mut exports = HashMap::new();
+                for name in &nested.declared_names {
+                    if let Some(fields) = nested.env.get_struct_fields(name) {
+                        exports.insert(
+                            name.clone(),
+                            TypeInfo::Struct { name: name.clone(), fields: fields.clone() },
+                        );
+                    } else if let Some(ty) = nested.env.lookup(name) {
+                        exports.insert(name.clone(), ty.clone());
+                    }
+                }
+                exports
+            }
+            Err(_) => {
+                self.errors.push(SemanticError::new(
+                    "RS017",
+                    format!("Failed to parse module '{}'", rel_path),
+                    use_span,
+                ));
+                if let Some(loader) = self.loader.as_mut() {
+                    loader.in_progress.remove(&full_path);
+                }
+                return None;
+            }
+        };
+
+        if let Some(loader) = self.loader.as_mut() {
+            loader.in_progress.remove(&full_path);
+            loader.cache.insert(full_path.clone(), exports.clone());
+        }
+
+        Some(exports)
+    }
+
     /// Get the type environment
     pub fn get_env(&self) -> &TypeEnv {
         &self.env
@@ -90,7 +418,7 @@ impl Resolver {
 
     fn resolve_plugin(&mut self, plugin: &PluginDecl) {
         // Define the plugin in scope
-        self.env.define(
+        self.define_value(
             plugin.name.clone(),
             TypeInfo::Struct {
                 name: plugin.name.clone(),
@@ -129,7 +457,7 @@ impl Resolver {
 
     fn resolve_writer(&mut self, writer: &WriterDecl) {
         // Same as plugin for now
-        self.env.define(
+        self.define_value(
             writer.name.clone(),
             TypeInfo::Struct {
                 name: writer.name.clone(),
@@ -199,6 +527,7 @@ impl Resolver {
             let ty = ast_type_to_type_info(&field.ty);
             fields.insert(field.name.clone(), ty);
         }
+        self.declared_names.insert(s.name.clone());
         self.env.define_struct(s.name.clone(), fields);
     }
 
@@ -210,6 +539,7 @@ impl Resolver {
             });
             variants.insert(variant.name.clone(), fields);
         }
+        self.declared_names.insert(e.name.clone());
         self.env.define_enum(e.name.clone(), variants);
     }
 
@@ -220,6 +550,7 @@ impl Resolver {
             .as_ref()
             .map(ast_type_to_type_info)
             .unwrap_or(TypeInfo::Unit);
+        self.declared_names.insert(f.name.clone());
         self.env.define_function(f.name.clone(), params, ret);
     }
 
@@ -228,6 +559,7 @@ impl Resolver {
 
         // Define parameters
         for param in &f.params {
+            self.resolve_type_ref(&param.ty, param.span);
             let ty = ast_type_to_type_info(&param.ty);
             if self.env.is_defined_in_current_scope(&param.name) {
                 self.errors.push(SemanticError::new(
@@ -236,7 +568,7 @@ impl Resolver {
                     param.span,
                 ));
             } else {
-                self.env.define(param.name.clone(), ty);
+                self.define_value(param.name.clone(), ty);
             }
         }
 
@@ -270,10 +602,11 @@ impl Resolver {
                 }
 
                 // Resolve the pattern and define variables
-                self.resolve_pattern(&let_stmt.pattern);
+                self.resolve_pattern(&let_stmt.pattern, let_stmt.span);
 
                 // Determine type
                 let ty = if let Some(ref type_ann) = let_stmt.ty {
+                    self.resolve_type_ref(type_ann, let_stmt.span);
                     ast_type_to_type_info(type_ann)
                 } else {
                     // Type will be inferred during type checking
@@ -281,7 +614,15 @@ impl Resolver {
                 };
 
                 // Define variables from the pattern
-                self.define_pattern(&let_stmt.pattern, ty);
+                self.define_pattern(&let_stmt.pattern, ty, let_stmt.span);
+
+                // `let PATTERN = EXPR else { ... };` - the else block runs
+                // in the enclosing scope (the pattern didn't match, so it
+                // has none of the pattern's bindings) and must diverge, but
+                // its own names still need resolving like any other block.
+                if let Some(ref else_block) = let_stmt.else_block {
+                    self.resolve_block(else_block);
+                }
             }
 
             Stmt::Const(const_stmt) => {
@@ -296,12 +637,13 @@ impl Resolver {
                 }
 
                 let ty = if let Some(ref type_ann) = const_stmt.ty {
+                    self.resolve_type_ref(type_ann, const_stmt.span);
                     ast_type_to_type_info(type_ann)
                 } else {
                     self.env.fresh_var()
                 };
 
-                self.env.define(const_stmt.name.clone(), ty);
+                self.define_value(const_stmt.name.clone(), ty);
             }
 
             Stmt::Expr(expr_stmt) => {
@@ -313,7 +655,7 @@ impl Resolver {
                 self.env.push_scope();
                 // If this is an if-let, resolve the pattern to bind variables
                 if let Some(ref pattern) = if_stmt.pattern {
-                    self.resolve_pattern(pattern);
+                    self.resolve_pattern(pattern, if_stmt.span);
                 }
                 self.resolve_block(&if_stmt.then_branch);
                 self.env.pop_scope();
@@ -336,7 +678,7 @@ impl Resolver {
                 self.resolve_expr(&match_stmt.scrutinee);
                 for arm in &match_stmt.arms {
                     self.env.push_scope();
-                    self.resolve_pattern(&arm.pattern);
+                    self.resolve_pattern(&arm.pattern, arm.span);
                     self.resolve_expr(&arm.body);
                     self.env.pop_scope();
                 }
@@ -347,21 +689,31 @@ impl Resolver {
                 self.env.push_scope();
                 // Define variables from pattern
                 // Use Unknown type for loop variables (type checker will refine this)
-                self.define_pattern(&for_stmt.pattern, TypeInfo::Unknown);
+                self.define_pattern(&for_stmt.pattern, TypeInfo::Unknown, for_stmt.span);
+                self.loop_labels.push(for_stmt.label.as_ref().map(|l| l.name.clone()));
                 self.resolve_block(&for_stmt.body);
+                self.loop_labels.pop();
                 self.env.pop_scope();
             }
 
             Stmt::While(while_stmt) => {
                 self.resolve_expr(&while_stmt.condition);
                 self.env.push_scope();
+                // If this is a while-let, resolve the pattern to bind variables
+                if let Some(ref pattern) = while_stmt.pattern {
+                    self.resolve_pattern(pattern, while_stmt.span);
+                }
+                self.loop_labels.push(while_stmt.label.as_ref().map(|l| l.name.clone()));
                 self.resolve_block(&while_stmt.body);
+                self.loop_labels.pop();
                 self.env.pop_scope();
             }
 
             Stmt::Loop(loop_stmt) => {
                 self.env.push_scope();
+                self.loop_labels.push(loop_stmt.label.as_ref().map(|l| l.name.clone()));
                 self.resolve_block(&loop_stmt.body);
+                self.loop_labels.pop();
                 self.env.pop_scope();
             }
 
@@ -371,7 +723,15 @@ impl Resolver {
                 }
             }
 
-            Stmt::Break(_) | Stmt::Continue(_) => {}
+            Stmt::Break(break_stmt) => {
+                self.check_loop_label(&break_stmt.label, break_stmt.span);
+                if let Some(ref value) = break_stmt.value {
+                    self.resolve_expr(value);
+                }
+            }
+            Stmt::Continue(continue_stmt) => {
+                self.check_loop_label(&continue_stmt.label, continue_stmt.span);
+            }
 
             Stmt::Traverse(traverse_stmt) => {
                 // Resolve the target expression
@@ -386,13 +746,13 @@ impl Resolver {
                         // Resolve state variables
                         for let_stmt in &inline.state {
                             self.resolve_expr(&let_stmt.init);
-                            self.resolve_pattern(&let_stmt.pattern);
+                            self.resolve_pattern(&let_stmt.pattern, let_stmt.span);
                             let ty = if let Some(ref type_ann) = let_stmt.ty {
                                 ast_type_to_type_info(type_ann)
                             } else {
                                 self.env.fresh_var()
                             };
-                            self.define_pattern(&let_stmt.pattern, ty);
+                            self.define_pattern(&let_stmt.pattern, ty, let_stmt.span);
                         }
 
                         // Resolve methods
@@ -403,30 +763,51 @@ impl Resolver {
                         self.env.pop_scope();
                     }
                     crate::parser::TraverseKind::Delegated(visitor_name) => {
-                        // Check if the visitor exists (would need to track plugin definitions)
-                        // For now, just note it for later validation
-                        let _ = visitor_name;
+                        // Visitors are just functions declared alongside the traverse
+                        // site (inside the same plugin/writer/module), so the existing
+                        // declare-then-resolve passes in resolve_plugin/resolve_writer/
+                        // resolve_module already collect every visitor name before any
+                        // function body - including this one - is resolved. A delegated
+                        // visitor is valid only if it names an already-declared function.
+                        let is_function = matches!(
+                            self.env.lookup(visitor_name),
+                            Some(TypeInfo::Function { .. })
+                        );
+                        if !is_function {
+                            let suggestion = self.suggest(visitor_name);
+                            let message = match &suggestion {
+                                Some(candidate) => format!(
+                                    "Traverse visitor '{}' is not defined (help: did you mean `{}`?)",
+                                    visitor_name, candidate
+                                ),
+                                None => format!("Traverse visitor '{}' is not defined", visitor_name),
+                            };
+                            self.errors.push(SemanticError::new("RS018", message, traverse_stmt.span));
+                            if let Some(candidate) = suggestion {
+                                self.suggestions.push((traverse_stmt.span, candidate));
+                            }
+                        }
                     }
                 }
             }
         }
     }
 
-    fn resolve_pattern(&mut self, pattern: &Pattern) {
+    fn resolve_pattern(&mut self, pattern: &Pattern, span: Span) {
         match pattern {
             Pattern::Ident(name) => {
                 // Bind the pattern variable
                 let var_type = self.env.fresh_var();
-                self.env.define(name.clone(), var_type);
+                self.define_value(name.clone(), var_type);
             }
             Pattern::Tuple(patterns) => {
                 for pat in patterns {
-                    self.resolve_pattern(pat);
+                    self.resolve_pattern(pat, span);
                 }
             }
             Pattern::Array(patterns) => {
                 for pat in patterns {
-                    self.resolve_pattern(pat);
+                    self.resolve_pattern(pat, span);
                 }
             }
             Pattern::Object(props) => {
@@ -434,52 +815,142 @@ impl Resolver {
                     match prop {
                         crate::parser::ObjectPatternProp::Shorthand(name) => {
                             let var_type = self.env.fresh_var();
-                            self.env.define(name.clone(), var_type);
+                            self.define_value(name.clone(), var_type);
                         }
                         crate::parser::ObjectPatternProp::KeyValue { value, .. } => {
-                            self.resolve_pattern(value);
+                            self.resolve_pattern(value, span);
                         }
                         crate::parser::ObjectPatternProp::Rest(name) => {
                             let var_type = self.env.fresh_var();
-                            self.env.define(name.clone(), var_type);
+                            self.define_value(name.clone(), var_type);
                         }
                         crate::parser::ObjectPatternProp::Or(patterns) => {
+                            self.check_or_pattern_bindings(patterns, span);
                             // For OR patterns in object props, resolve all branches
                             for pat in patterns {
-                                self.resolve_pattern(pat);
+                                self.resolve_pattern(pat, span);
                             }
                         }
                     }
                 }
             }
             Pattern::Rest(inner) => {
-                self.resolve_pattern(inner);
+                self.resolve_pattern(inner, span);
             }
             Pattern::Struct { fields, .. } => {
                 for (_, pat) in fields {
-                    self.resolve_pattern(pat);
+                    self.resolve_pattern(pat, span);
                 }
             }
             Pattern::Or(patterns) => {
+                self.check_or_pattern_bindings(patterns, span);
                 for pat in patterns {
-                    self.resolve_pattern(pat);
+                    self.resolve_pattern(pat, span);
                 }
             }
             Pattern::Variant { inner, .. } => {
                 // Resolve inner pattern if present (e.g., Some(x) -> resolve x)
                 if let Some(inner_pat) = inner {
-                    self.resolve_pattern(inner_pat);
+                    self.resolve_pattern(inner_pat, span);
+                }
+            }
+            Pattern::Literal(_) | Pattern::Wildcard => {}
+        }
+    }
+
+    /// Collect the set of identifier names `pattern` would bind, recursing
+    /// through tuple/struct/variant patterns the same way `resolve_pattern`
+    /// and `define_pattern` do. A nested `Or` only contributes its first
+    /// alternative - comparing whole alternatives against each other is
+    /// `check_or_pattern_bindings`'s job, one level at a time.
+    fn pattern_bound_names(&self, pattern: &Pattern) -> std::collections::BTreeSet<String> {
+        let mut names = std::collections::BTreeSet::new();
+        match pattern {
+            Pattern::Ident(name) => {
+                names.insert(name.clone());
+            }
+            Pattern::Tuple(patterns) | Pattern::Array(patterns) => {
+                for pat in patterns {
+                    names.extend(self.pattern_bound_names(pat));
+                }
+            }
+            Pattern::Object(props) => {
+                for prop in props {
+                    names.extend(self.object_prop_bound_names(prop));
+                }
+            }
+            Pattern::Rest(inner) => {
+                names.extend(self.pattern_bound_names(inner));
+            }
+            Pattern::Struct { fields, .. } => {
+                for (_, pat) in fields {
+                    names.extend(self.pattern_bound_names(pat));
+                }
+            }
+            Pattern::Or(patterns) => {
+                if let Some(first) = patterns.first() {
+                    names.extend(self.pattern_bound_names(first));
+                }
+            }
+            Pattern::Variant { inner, .. } => {
+                if let Some(inner_pat) = inner {
+                    names.extend(self.pattern_bound_names(inner_pat));
                 }
             }
             Pattern::Literal(_) | Pattern::Wildcard => {}
         }
+        names
+    }
+
+    fn object_prop_bound_names(
+        &self,
+        prop: &crate::parser::ObjectPatternProp,
+    ) -> std::collections::BTreeSet<String> {
+        let mut names = std::collections::BTreeSet::new();
+        match prop {
+            crate::parser::ObjectPatternProp::Shorthand(name)
+            | crate::parser::ObjectPatternProp::Rest(name) => {
+                names.insert(name.clone());
+            }
+            crate::parser::ObjectPatternProp::KeyValue { value, .. } => {
+                names.extend(self.pattern_bound_names(value));
+            }
+            crate::parser::ObjectPatternProp::Or(patterns) => {
+                if let Some(first) = patterns.first() {
+                    names.extend(self.pattern_bound_names(first));
+                }
+            }
+        }
+        names
+    }
+
+    /// Validate that every alternative of an or-pattern binds the exact
+    /// same set of names - following rustc's rule, since a branch that's
+    /// missing a binding the others introduce would leave that variable
+    /// undefined whenever that branch is the one taken. Reports RS019 at
+    /// `span` for each name that isn't common to every alternative.
+    fn check_or_pattern_bindings(&mut self, alternatives: &[Pattern], span: Span) {
+        let Some((first, rest)) = alternatives.split_first() else {
+            return;
+        };
+        let first_names = self.pattern_bound_names(first);
+        for alternative in rest {
+            let names = self.pattern_bound_names(alternative);
+            for missing in first_names.symmetric_difference(&names) {
+                self.errors.push(SemanticError::new(
+                    "RS019",
+                    format!("variable `{}` is not bound in all patterns", missing),
+                    span,
+                ));
+            }
+        }
     }
 
     /// Define variables from a pattern with a given type info
-    fn define_pattern(&mut self, pattern: &Pattern, type_info: TypeInfo) {
+    fn define_pattern(&mut self, pattern: &Pattern, type_info: TypeInfo, span: Span) {
         match pattern {
             Pattern::Ident(name) => {
-                self.env.define(name.clone(), type_info);
+                self.define_value(name.clone(), type_info);
             }
             Pattern::Tuple(patterns) => {
                 // Extract tuple element types if available
@@ -489,13 +960,13 @@ impl Resolver {
                             let elem_type = elem_types.get(i)
                                 .cloned()
                                 .unwrap_or(TypeInfo::Unknown);
-                            self.define_pattern(pat, elem_type);
+                            self.define_pattern(pat, elem_type, span);
                         }
                     }
                     _ => {
                         // If not a tuple type, give all elements Unknown type
                         for pat in patterns {
-                            self.define_pattern(pat, TypeInfo::Unknown);
+                            self.define_pattern(pat, TypeInfo::Unknown, span);
                         }
                     }
                 }
@@ -510,10 +981,14 @@ impl Resolver {
                 // Rest pattern not yet implemented
             }
             Pattern::Or(patterns) => {
-                // For OR patterns, all branches must bind the same variables with same types
-                // For now, just define variables from the first pattern
+                // All alternatives must bind the same names - report any
+                // mismatch (RS019), then still commit the first
+                // alternative's bindings so the rest of the block has
+                // *something* to type-check against, same fallback the
+                // type checker's equivalent check uses.
+                self.check_or_pattern_bindings(patterns, span);
                 if let Some(first) = patterns.first() {
-                    self.define_pattern(first, type_info);
+                    self.define_pattern(first, type_info, span);
                 }
             }
             Pattern::Struct { .. } | Pattern::Variant { .. } | Pattern::Literal(_) | Pattern::Wildcard => {
@@ -533,11 +1008,18 @@ impl Resolver {
                     // Check if it's a known AST node type (used in matches!)
                     let is_ast_type = get_node_mapping(&ident.name).is_some();
                     if !is_special && !is_ast_type {
-                        self.errors.push(SemanticError::new(
-                            "RS006",
-                            format!("Undefined variable: {}", ident.name),
-                            ident.span,
-                        ));
+                        let suggestion = self.suggest(&ident.name);
+                        let message = match &suggestion {
+                            Some(candidate) => format!(
+                                "Undefined variable: {} (help: did you mean `{}`?)",
+                                ident.name, candidate
+                            ),
+                            None => format!("Undefined variable: {}", ident.name),
+                        };
+                        self.errors.push(SemanticError::new("RS006", message, ident.span));
+                        if let Some(candidate) = suggestion {
+                            self.suggestions.push((ident.span, candidate));
+                        }
                     }
                 }
             }
@@ -560,7 +1042,27 @@ impl Resolver {
 
             Expr::Member(member) => {
                 self.resolve_expr(&member.object);
-                // Property name doesn't need resolution
+                // Access through a known built-in module is validated
+                // against its export table; anything else (a struct field,
+                // a file-module member) has no registry to check against
+                // here, so it's left to the type checker.
+                if let Expr::Ident(ident) = member.object.as_ref() {
+                    let module_name = match self.env.lookup(&ident.name) {
+                        Some(TypeInfo::Module { name }) => Some(name.clone()),
+                        _ => None,
+                    };
+                    if let Some(name) = module_name {
+                        if let Some(exports) = module_exports(&name) {
+                            if !exports.contains_key(&member.property) {
+                                self.errors.push(SemanticError::new(
+                                    "RS007",
+                                    format!("Module '{}' has no member '{}'", name, member.property),
+                                    member.span,
+                                ));
+                            }
+                        }
+                    }
+                }
             }
 
             Expr::Index(index) => {
@@ -569,18 +1071,31 @@ impl Resolver {
             }
 
             Expr::StructInit(init) => {
-                // Check struct exists
+                // Check the struct exists in the type namespace (or was
+                // brought in by an import whose shape we can't see yet) -
+                // a same-named local *value* doesn't count, so a variable
+                // can no longer mask a missing struct here.
                 if self.env.get_struct_fields(&init.name).is_none()
-                   && self.env.lookup(&init.name).is_none() {
-                    self.errors.push(SemanticError::new(
-                        "RS007",
-                        format!("Unknown struct: {}", init.name),
-                        init.span,
-                    ));
+                   && !self.imported_names.contains(&init.name) {
+                    let suggestion = self.suggest(&init.name);
+                    let message = match &suggestion {
+                        Some(candidate) => format!(
+                            "Unknown struct: {} (help: did you mean `{}`?)",
+                            init.name, candidate
+                        ),
+                        None => format!("Unknown struct: {}", init.name),
+                    };
+                    self.errors.push(SemanticError::new("RS007", message, init.span));
+                    if let Some(candidate) = suggestion {
+                        self.suggestions.push((init.span, candidate));
+                    }
                 }
                 for (_, value) in &init.fields {
                     self.resolve_expr(value);
                 }
+                if let Some(rest) = &init.rest {
+                    self.resolve_expr(rest);
+                }
             }
 
             Expr::VecInit(vec_init) => {
@@ -589,6 +1104,31 @@ impl Resolver {
                 }
             }
 
+            Expr::Tuple(tuple) => {
+                for elem in &tuple.elements {
+                    self.resolve_expr(elem);
+                }
+            }
+
+            Expr::Array(array) => {
+                for elem in &array.elements {
+                    self.resolve_expr(elem);
+                }
+            }
+
+            Expr::ArrayRepeat(repeat) => {
+                self.resolve_expr(&repeat.value);
+                self.resolve_expr(&repeat.count);
+            }
+
+            Expr::MacroCall(macro_call) => {
+                // Brace-delimited macros keep their body as raw tokens, so
+                // there are no sub-expressions to resolve there.
+                for arg in &macro_call.args {
+                    self.resolve_expr(arg);
+                }
+            }
+
             Expr::If(if_expr) => {
                 self.resolve_expr(&if_expr.condition);
                 self.env.push_scope();
@@ -605,7 +1145,7 @@ impl Resolver {
                 self.resolve_expr(&match_expr.scrutinee);
                 for arm in &match_expr.arms {
                     self.env.push_scope();
-                    self.resolve_pattern(&arm.pattern);
+                    self.resolve_pattern(&arm.pattern, arm.span);
                     self.resolve_expr(&arm.body);
                     self.env.pop_scope();
                 }
@@ -614,8 +1154,11 @@ impl Resolver {
             Expr::Closure(closure) => {
                 self.env.push_scope();
                 for param in &closure.params {
-                    let var_type = self.env.fresh_var();
-                    self.env.define(param.clone(), var_type);
+                    let ty = match param.ty {
+                        Type::Inferred => self.env.fresh_var(),
+                        ref ty => ast_type_to_type_info(ty),
+                    };
+                    self.define_value(param.name.clone(), ty);
                 }
                 self.resolve_expr(&closure.body);
                 self.env.pop_scope();
@@ -666,6 +1209,11 @@ impl Resolver {
             }
 
             Expr::Literal(_) => {}
+
+            // Sentinel left behind by the parser's error recovery - the
+            // broken sub-expression has already been reported as a parse
+            // error, so there's nothing further to resolve here.
+            Expr::Error(_) => {}
         }
     }
 }
@@ -675,3 +1223,97 @@ impl Default for Resolver {
         Self::new()
     }
 }
+
+/// The top-level items of whichever kind of source file `decl` came from -
+/// a plugin, a writer, or a plain module all declare structs/enums/
+/// functions the same way, so a file loaded purely for its exports doesn't
+/// need to care which one it is. An interface file has no such items.
+fn top_level_items(decl: &TopLevelDecl) -> &[PluginItem] {
+    match decl {
+        TopLevelDecl::Plugin(p) => &p.body,
+        TopLevelDecl::Writer(w) => &w.body,
+        TopLevelDecl::Module(m) => &m.items,
+        TopLevelDecl::Interface(_) => &[],
+    }
+}
+
+/// The functions and types a built-in module (`fs`, `json`, `parser`,
+/// `codegen`) exports, or `None` if `module` isn't one of them (a file
+/// module, or an invalid path `resolve_use` has already reported).
+///
+/// This crate only consumes these modules - their actual implementation is
+/// part of the runtime, not this source tree - so the signatures below are
+/// a best-effort seed covering the operations a module with that name
+/// would be expected to expose, not a verified contract. Treat a missing
+/// entry as "not yet catalogued" rather than "doesn't exist"; only a
+/// present-but-wrong signature here would be a real bug.
+fn module_exports(module: &str) -> Option<HashMap<String, TypeInfo>> {
+    let mut exports = HashMap::new();
+    match module {
+        "fs" => {
+            exports.insert(
+                "read_file".to_string(),
+                TypeInfo::Function { params: vec![TypeInfo::Str], ret: Box::new(TypeInfo::Str) },
+            );
+            exports.insert(
+                "write_file".to_string(),
+                TypeInfo::Function { params: vec![TypeInfo::Str, TypeInfo::Str], ret: Box::new(TypeInfo::Unit) },
+            );
+            exports.insert(
+                "exists".to_string(),
+                TypeInfo::Function { params: vec![TypeInfo::Str], ret: Box::new(TypeInfo::Bool) },
+            );
+        }
+        "json" => {
+            exports.insert(
+                "parse".to_string(),
+                TypeInfo::Function { params: vec![TypeInfo::Str], ret: Box::new(TypeInfo::Unknown) },
+            );
+            exports.insert(
+                "stringify".to_string(),
+                TypeInfo::Function { params: vec![TypeInfo::Unknown], ret: Box::new(TypeInfo::Str) },
+            );
+        }
+        "parser" => {
+            exports.insert(
+                "parse".to_string(),
+                TypeInfo::Function {
+                    params: vec![TypeInfo::Str],
+                    ret: Box::new(TypeInfo::AstNode("Program".to_string())),
+                },
+            );
+        }
+        "codegen" => {
+            exports.insert(
+                "generate".to_string(),
+                TypeInfo::Function {
+                    params: vec![TypeInfo::AstNode("Program".to_string())],
+                    ret: Box::new(TypeInfo::Str),
+                },
+            );
+        }
+        _ => return None,
+    }
+    Some(exports)
+}
+
+/// Classic Wagner-Fischer edit distance: the minimum number of single-
+/// character insertions, deletions, and substitutions to turn `a` into `b`.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut prev_diag = row[0];
+        row[0] = i;
+        for j in 1..=b.len() {
+            let deletion = row[j] + 1;
+            let insertion = row[j - 1] + 1;
+            let substitution = prev_diag + usize::from(a[i - 1] != b[j - 1]);
+            prev_diag = row[j];
+            row[j] = deletion.min(insertion).min(substitution);
+        }
+    }
+    row[b.len()]
+}
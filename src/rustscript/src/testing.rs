@@ -0,0 +1,266 @@
+//! Snapshot testing for generated output.
+//!
+//! Walks a directory for RustScript source files, compiles each through the
+//! full `generate` pipeline, and compares the result against committed
+//! `<file>.babel.snap` / `<file>.swc.snap` snapshots sitting next to it.
+
+use rustscript::{analyze, generate, lower, Lexer, Parser, Target};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub enum SnapshotCheck {
+    Match,
+    Created,
+    Updated,
+    Mismatch(String),
+    ParseFailed(String),
+    NotApplicable,
+}
+
+pub struct TestCaseResult {
+    pub path: PathBuf,
+    pub babel: SnapshotCheck,
+    pub swc: SnapshotCheck,
+}
+
+/// Recursively collect files under `root` whose path (relative to `root`,
+/// with `/` separators) matches `include` and, if given, doesn't match
+/// `exclude`. Snapshot files themselves are always skipped.
+pub fn collect_rustscript_files(root: &Path, include: &str, exclude: Option<&str>) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    walk_dir(root, root, include, exclude, &mut files);
+    files.sort();
+    files
+}
+
+fn walk_dir(root: &Path, dir: &Path, include: &str, exclude: Option<&str>, files: &mut Vec<PathBuf>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            walk_dir(root, &path, include, exclude, files);
+            continue;
+        }
+
+        let relative = path.strip_prefix(root).unwrap_or(&path).to_string_lossy().replace('\\', "/");
+        if relative.ends_with(".snap") {
+            continue;
+        }
+        if !glob_match(include, &relative) {
+            continue;
+        }
+        if exclude.is_some_and(|pattern| glob_match(pattern, &relative)) {
+            continue;
+        }
+
+        files.push(path);
+    }
+}
+
+/// Minimal glob matcher: `*` matches any run of characters except `/`, `**`
+/// matches any run of characters including `/`, `?` matches a single
+/// character except `/`. Good enough for `include`/`exclude` patterns like
+/// `**/*.rs` or `fixtures/*.rs` without pulling in a glob crate.
+pub fn glob_match(pattern: &str, candidate: &str) -> bool {
+    glob_match_bytes(pattern.as_bytes(), candidate.as_bytes())
+}
+
+fn glob_match_bytes(pattern: &[u8], path: &[u8]) -> bool {
+    match pattern.first() {
+        None => path.is_empty(),
+        Some(b'*') if pattern.get(1) == Some(&b'*') => {
+            let mut rest = &pattern[2..];
+            if rest.first() == Some(&b'/') {
+                rest = &rest[1..];
+            }
+            glob_match_bytes(rest, path) || (!path.is_empty() && glob_match_bytes(pattern, &path[1..]))
+        }
+        Some(b'*') => {
+            let rest = &pattern[1..];
+            glob_match_bytes(rest, path)
+                || (!path.is_empty() && path[0] != b'/' && glob_match_bytes(pattern, &path[1..]))
+        }
+        Some(b'?') => !path.is_empty() && path[0] != b'/' && glob_match_bytes(&pattern[1..], &path[1..]),
+        Some(&c) => !path.is_empty() && path[0] == c && glob_match_bytes(&pattern[1..], &path[1..]),
+    }
+}
+
+/// Run every test case across the available cores. Falls back to serial
+/// execution for a single file or when parallelism can't be determined.
+pub fn run_tests_parallel(files: &[PathBuf], update: bool) -> Vec<TestCaseResult> {
+    let worker_count = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(files.len().max(1));
+
+    if worker_count <= 1 {
+        return files.iter().map(|file| run_test_case(file, update)).collect();
+    }
+
+    let chunk_size = (files.len() + worker_count - 1) / worker_count;
+    let mut results = Vec::with_capacity(files.len());
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = files
+            .chunks(chunk_size.max(1))
+            .map(|chunk| scope.spawn(move || chunk.iter().map(|file| run_test_case(file, update)).collect::<Vec<_>>()))
+            .collect();
+        for handle in handles {
+            if let Ok(chunk_results) = handle.join() {
+                results.extend(chunk_results);
+            }
+        }
+    });
+    results
+}
+
+fn run_test_case(path: &Path, update: bool) -> TestCaseResult {
+    let source = match fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) => return parse_failed(path, format!("could not read file: {}", e)),
+    };
+
+    let mut lexer = Lexer::new(&source);
+    let tokens = lexer.tokenize();
+    let mut parser = Parser::new(tokens);
+    let mut program = match parser.parse() {
+        Ok(p) => p,
+        Err(e) => {
+            return parse_failed(
+                path,
+                format!("parse error at {}:{}: {}", e.span.line, e.span.column, e.message),
+            )
+        }
+    };
+
+    let result = analyze(&program);
+    if !result.errors.is_empty() {
+        return parse_failed(path, format!("{} semantic error(s)", result.errors.len()));
+    }
+
+    lower(&mut program);
+    let generated = generate(&program, Target::Both);
+
+    TestCaseResult {
+        path: path.to_path_buf(),
+        babel: check_snapshot(path, "babel", generated.babel.as_deref(), update),
+        swc: check_snapshot(path, "swc", generated.swc.as_deref(), update),
+    }
+}
+
+fn parse_failed(path: &Path, message: String) -> TestCaseResult {
+    TestCaseResult {
+        path: path.to_path_buf(),
+        babel: SnapshotCheck::ParseFailed(message.clone()),
+        swc: SnapshotCheck::ParseFailed(message),
+    }
+}
+
+fn check_snapshot(path: &Path, kind: &str, generated: Option<&str>, update: bool) -> SnapshotCheck {
+    let generated = match generated {
+        Some(code) => code,
+        None => return SnapshotCheck::NotApplicable,
+    };
+
+    let snapshot_path = snapshot_path_for(path, kind);
+    match fs::read_to_string(&snapshot_path) {
+        Ok(existing) if existing == generated => SnapshotCheck::Match,
+        Ok(existing) => {
+            if update {
+                let _ = fs::write(&snapshot_path, generated);
+                SnapshotCheck::Updated
+            } else {
+                SnapshotCheck::Mismatch(unified_diff(&existing, generated))
+            }
+        }
+        Err(_) => {
+            if update {
+                let _ = fs::write(&snapshot_path, generated);
+                SnapshotCheck::Created
+            } else {
+                SnapshotCheck::Mismatch(unified_diff("", generated))
+            }
+        }
+    }
+}
+
+fn snapshot_path_for(path: &Path, kind: &str) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".{}.snap", kind));
+    path.with_file_name(file_name)
+}
+
+enum DiffOp<'a> {
+    Equal(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Line-based unified-style diff via an LCS backtrace - no external crate,
+/// fine for the size of generated codegen output this guards.
+fn unified_diff(old: &str, new: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+
+    let n = old_lines.len();
+    let m = new_lines.len();
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_lines[i] == new_lines[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old_lines[i] == new_lines[j] {
+            ops.push(DiffOp::Equal(old_lines[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push(DiffOp::Removed(old_lines[i]));
+            i += 1;
+        } else {
+            ops.push(DiffOp::Added(new_lines[j]));
+            j += 1;
+        }
+    }
+    while i < n {
+        ops.push(DiffOp::Removed(old_lines[i]));
+        i += 1;
+    }
+    while j < m {
+        ops.push(DiffOp::Added(new_lines[j]));
+        j += 1;
+    }
+
+    let mut out = String::new();
+    for op in ops {
+        match op {
+            DiffOp::Equal(line) => {
+                out.push_str("  ");
+                out.push_str(line);
+                out.push('\n');
+            }
+            DiffOp::Removed(line) => {
+                out.push_str("- ");
+                out.push_str(line);
+                out.push('\n');
+            }
+            DiffOp::Added(line) => {
+                out.push_str("+ ");
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+    }
+    out
+}